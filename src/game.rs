@@ -1,192 +1,893 @@
-use std::{
-    sync::mpsc::{self, Sender},
-    thread,
-    time::Duration,
-};
+use std::{collections::VecDeque, io, path::Path};
 
 use crate::{
-    ui,
+    ui::{self, SnakeGameWidget},
     utils::{
-        collectables::{AnyCollectable, CollectableType},
-        enums::{CurrentScreen, Direction, Event},
+        achievements::{ACHIEVEMENTS, AchievementStore},
+        animations::{
+            Animation, AnimationKind, COUNTDOWN_TICKS, DEATH_TICKS, FLASH_TICKS, SCORE_POPUP_TICKS,
+            Toast,
+        },
+        arena::{Arena, is_inside},
+        collectables::{
+            APPLE, CollectResult, CollectableId, GHOST, MAGNET, SHIELD, SpawnedCollectable,
+        },
+        config::GameConfig,
+        effects::{ActiveEffect, EffectDefinition, StackRule},
+        enums::{
+            CurrentScreen, DeathCause, Direction, Event, GameMode, LostEntry, MenuEntry, QuitEntry,
+            RenderStyle, SettingsEntry, SnakeSkin,
+        },
+        event_source::EventSource,
+        game_events::GameEvent,
+        hazards::{Hazard, MAX_HAZARDS, START_HAZARDS, get_hazard_move_interval},
+        helpers::{get_current_date, get_cycled},
+        lifetime_stats::{LifetimeStats, StatsStore},
+        registry::CollectableRegistry,
+        replay::{Replay, ReplayInput},
+        save_game::{SaveGame, SavedCollectable, SavedEffect},
         scores::{Score, ScoreManager},
+        scoring::Scoring,
+        settings::Settings,
+        sound::{Sound, SoundEvent, Volume},
+        spawn_table::SpawnTable,
+        stats::RunStats,
+        theme::{Theme, is_no_color_set},
     },
 };
 use crossterm::event::{self, KeyEvent};
-use rand::random_range;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use ratatui::DefaultTerminal;
+
+const PLAYER_NAME: &str = "you";
+/// Seconds the snake can pass through its body after the shield saved it from a tail hit
+const SHIELD_GRACE_SECONDS: u32 = 2;
 
 pub struct App {
     pub exit: bool,
     pub current_screen: CurrentScreen,
+    /// Screens below the current one together with their cursor, `Esc` goes back to the top one
+    screen_stack: Vec<(CurrentScreen, Option<usize>)>,
     pub menu_cursor: Option<usize>,
     pub direction: Direction,
     pub snake: Vec<(f64, f64)>,
     blocked: bool,
     pub field_size: (u32, u32),
     pub tick: bool,
-    pub collectables: Vec<AnyCollectable>,
-    pub game_speed: u32,
+    pub highscore_scroll: usize,
+    pub collectables: Vec<SpawnedCollectable>,
+    pub effects: Vec<ActiveEffect>,
+    /// Blocks wandering over the field in Survival mode
+    pub hazards: Vec<Hazard>,
+    /// Walls that close in during a Shrinking Arena round
+    pub arena: Option<Arena>,
+    pub animations: Vec<Animation>,
+    /// Game ticks left until the snake starts moving
+    pub countdown: u32,
+    pub game_speed: i32,
+    move_counter: u32,
     pub round_time: u64,
     pub random_item_timer: u32,
+    /// Config of the current round, used again when the round is retried
+    pub config: GameConfig,
+    /// Every random decision of a round comes from here, so replays play out the same way
+    ///
+    /// Unlike the standard generator its state can be saved together with the round.
+    pub rng: ChaCha12Rng,
+    pub stats: RunStats,
+    pub scoring: Scoring,
+    /// Game ticks since the round started, not counting the countdown
+    round_ticks: u64,
+    /// Direction changes of the current round, in the order they happened
+    recorded_inputs: Vec<ReplayInput>,
+    /// Inputs that are still to come while a replay is watched
+    replay_inputs: Option<VecDeque<ReplayInput>>,
+    /// The round is a daily challenge that was already attempted today, so it is not ranked
+    practice_round: bool,
+    /// The round was saved on quit and continued later
+    resumed: bool,
+    /// Round that was saved on quit and can be continued from the menu
+    saved_game: Option<SaveGame>,
+    /// Whether an unfinished round is saved on quit, off for games that never touch the disk
+    persistent: bool,
+    /// Plays the sound effects, silent unless the game runs in a terminal of its own
+    sound: Sound,
+    /// Short message shown on the game over screen, like where a replay was saved
+    pub notice: Option<String>,
+    pub spawn_table: SpawnTable,
+    pub registry: CollectableRegistry,
     score_manager: ScoreManager,
+    stats_store: StatsStore,
+    achievements: AchievementStore,
+    /// Notifications in the corner of the screen, like unlocked achievements
+    pub toasts: Vec<Toast>,
+    pub settings: Settings,
+    pub theme: Theme,
+    /// Area the game was last rendered into, used to pick an automatic field size
+    pub last_area: Option<(u16, u16)>,
+    tick_count: u64,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
+
 impl App {
     pub fn new() -> Self {
+        let mut app = Self::with_score_manager(ScoreManager::new());
+        app.settings = Settings::load();
+        app.stats_store = StatsStore::load();
+        app.achievements = AchievementStore::load();
+        app.theme = Theme::load(&app.settings.theme);
+        app.saved_game = SaveGame::load();
+        app.persistent = true;
+        app.sound = Sound::new();
+        app
+    }
+
+    /// Creates a game that keeps its scores in the given manager,
+    /// use [`ScoreManager::in_memory`] for a game that never touches the disk
+    pub fn with_score_manager(score_manager: ScoreManager) -> Self {
+        let registry = CollectableRegistry::default();
+        let config = GameConfig::default();
         App {
             exit: false,
             current_screen: CurrentScreen::Menu,
+            screen_stack: vec![],
             menu_cursor: Some(0),
             direction: Direction::Up,
             snake: vec![(21.0, 20.0), (22.0, 20.0), (20.0, 20.0)],
             game_speed: 0,
+            move_counter: 0,
             blocked: true,
             field_size: (50, 25),
             tick: false,
+            highscore_scroll: 0,
             collectables: vec![],
+            effects: vec![],
+            hazards: vec![],
+            arena: None,
+            animations: vec![],
+            countdown: 0,
             round_time: 0,
             random_item_timer: 5,
-            score_manager: ScoreManager::new(),
+            spawn_table: SpawnTable::new(&config, 1, &registry),
+            rng: ChaCha12Rng::seed_from_u64(config.seed),
+            config,
+            stats: RunStats::default(),
+            scoring: Scoring::default(),
+            round_ticks: 0,
+            recorded_inputs: vec![],
+            replay_inputs: None,
+            practice_round: false,
+            resumed: false,
+            saved_game: None,
+            persistent: false,
+            sound: Sound::default(),
+            notice: None,
+            registry,
+            score_manager,
+            stats_store: StatsStore::default(),
+            achievements: AchievementStore::default(),
+            toasts: vec![],
+            settings: Settings::default(),
+            theme: Theme::default(),
+            last_area: None,
+            tick_count: 0,
         }
     }
 
+    /// Returns the score of the current round, what it counts depends on the game mode
     pub fn get_score(&self) -> i32 {
-        return self.snake.len() as i32 - 5;
+        match self.config.game_mode {
+            GameMode::Classic | GameMode::Daily | GameMode::Survival | GameMode::ShrinkingArena => {
+                self.scoring.total
+            }
+            GameMode::TimeAttack => self.stats.apples_eaten as i32,
+            GameMode::AppleRush => self.round_ticks as i32,
+        }
+    }
+
+    /// Returns the game ticks since the round started, not counting the countdown
+    pub fn get_round_ticks(&self) -> u64 {
+        self.round_ticks
+    }
+
+    /// Returns the current level, which increases every minute of a round
+    pub fn get_level(&self) -> u32 {
+        1 + (self.round_time / 60) as u32
+    }
+
+    /// Returns the leaderboard of the current round, or of the selected mode outside of a round
+    pub fn get_highscores(&self) -> &[Score] {
+        match (self.get_leaderboard_mode(), &self.config.challenge_date) {
+            (GameMode::Daily, Some(date)) => self.score_manager.get_daily_scores(date),
+            (game_mode, _) => self.score_manager.get_scores(game_mode),
+        }
+    }
+
+    /// Returns the heading of the leaderboard returned by [`App::get_highscores`]
+    pub fn get_highscores_title(&self) -> String {
+        match (self.get_leaderboard_mode(), &self.config.challenge_date) {
+            (GameMode::Daily, Some(date)) => format!("Daily {}", date),
+            (GameMode::Classic, _) => String::from("Highscores"),
+            (game_mode, _) => game_mode.get_label().to_string(),
+        }
+    }
+
+    fn get_leaderboard_mode(&self) -> GameMode {
+        match self.current_screen {
+            CurrentScreen::Main | CurrentScreen::Lost => self.config.game_mode,
+            _ => self.settings.game_mode,
+        }
     }
 
-    pub fn get_highscores(&self) -> &Vec<Score> {
-        return self.score_manager.get_scores();
+    /// Returns whether the score of the current round goes on a leaderboard
+    pub fn is_ranked(&self) -> bool {
+        !self.is_replaying() && !self.practice_round
     }
 
-    pub fn run(&mut self) -> Result<(), std::io::Error> {
-        let mut terminal = ratatui::init();
+    /// Returns the lifetime stats of the player, if they finished a round before
+    pub fn get_lifetime_stats(&self) -> Option<&LifetimeStats> {
+        self.stats_store.get(PLAYER_NAME)
+    }
+
+    pub fn get_achievements(&self) -> &AchievementStore {
+        &self.achievements
+    }
 
-        let (event_tx, event_rx) = mpsc::channel::<Event>();
+    /// Runs the game in the terminal until the player quits
+    ///
+    /// The terminal is restored however the game ends. A panic restores it through the hook
+    /// that [`ratatui::try_init`] installs, any other error is returned after it was restored.
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut terminal = init_terminal()?;
+
+        // The threads of the source are stopped when it is dropped at the end of this call
+        let mut event_source = EventSource::new();
+        let result = self.run_event_loop(&mut terminal, &mut event_source);
+        drop(event_source);
+        ratatui::restore();
+        result
+    }
 
-        self.create_threads(event_tx);
-        let mut ms = 0;
-        let mut counter = 3;
+    fn run_event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        event_source: &mut EventSource,
+    ) -> io::Result<()> {
         while !self.exit {
-            let _ = terminal.draw(|frame| {
-                ui::render(frame, &self);
-            });
-            if let Ok(event) = event_rx.try_recv() {
-                match event {
-                    Event::Input(key_event) => {
-                        self.handle_input_events(key_event);
-                    }
-                    Event::GameTick => {
-                        ms += 50;
-                        counter -= 1;
-                        self.on_tick(&mut counter);
-                        if counter == 0 {
-                            counter = 3;
-                            self.tick = !self.tick;
-                        }
-                        if ms >= 1000 {
-                            self.on_second_update();
-                            ms = 0;
-                        }
-                    }
+            terminal.draw(|frame| {
+                ui::render(frame, self);
+            })?;
+            for event in event_source.next_events()? {
+                self.handle_event(event);
+                if self.exit {
+                    break;
                 }
             }
         }
-        ratatui::restore();
         Ok(())
     }
 
-    fn create_threads(&self, event_tx: Sender<Event>) {
-        let tx_to_input_events = event_tx.clone();
-        thread::spawn(move || {
-            loop {
-                match crossterm::event::read().unwrap() {
-                    crossterm::event::Event::Key(key_event) => {
-                        tx_to_input_events.send(Event::Input(key_event)).unwrap()
-                    }
-                    _ => {}
-                }
+    /// Runs the game like [`App::run`], but on a tokio runtime
+    ///
+    /// Input comes from crossterm's [`EventStream`](event::EventStream) and ticks from an
+    /// interval that skips the ticks it missed. The future is not `Send`, so it has to run
+    /// on the current thread, through `block_on` or `spawn_local` in a `LocalSet`.
+    #[cfg(feature = "tokio")]
+    pub async fn run_async(&mut self) -> io::Result<()> {
+        let (_event_tx, event_rx) = tokio::sync::mpsc::channel(1);
+        self.run_async_with(event_rx).await
+    }
+
+    /// Runs the game like [`App::run_async`] and also plays the events that other tasks,
+    /// like a network connection or a bot, send into `events`
+    #[cfg(feature = "tokio")]
+    pub async fn run_async_with(
+        &mut self,
+        mut events: tokio::sync::mpsc::Receiver<Event>,
+    ) -> io::Result<()> {
+        use crate::utils::event_source::TICK_RATE;
+        use futures::StreamExt;
+        use tokio::time::{MissedTickBehavior, interval};
+
+        let mut terminal = init_terminal()?;
+        let mut input = event::EventStream::new();
+        let mut ticker = interval(TICK_RATE);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let result = loop {
+            if self.exit {
+                break Ok(());
             }
-        });
-        let tx_to_background_progress_events = event_tx.clone();
-        thread::spawn(move || {
-            loop {
-                thread::sleep(Duration::from_millis(50));
-                tx_to_background_progress_events
-                    .send(Event::GameTick)
-                    .unwrap();
+            if let Err(err) = terminal.draw(|frame| ui::render(frame, self)) {
+                break Err(err);
             }
-        });
+            tokio::select! {
+                _ = ticker.tick() => self.on_game_tick(),
+                input_event = input.next() => match input_event {
+                    Some(Ok(event::Event::Key(key_event))) => self.handle_input_events(key_event),
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => break Err(err),
+                    None => break Err(io::Error::other("the terminal input ended")),
+                },
+                // Once every sender is gone this branch is left out
+                Some(event) = events.recv() => self.handle_event(event),
+            }
+        };
+        ratatui::restore();
+        result
     }
 
-    fn handle_input_events(&mut self, key_event: KeyEvent) {
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Input(key_event) => {
+                self.handle_input_events(key_event);
+            }
+            Event::GameTick => self.on_game_tick(),
+        }
+    }
+
+    /// Advances the game by one tick of 50 milliseconds
+    pub fn on_game_tick(&mut self) {
+        self.tick_count += 1;
+        self.animations.retain_mut(|animation| !animation.on_tick());
+        self.toasts.retain_mut(|toast| !toast.on_tick());
+        if self.tick_count.is_multiple_of(3) {
+            self.tick = !self.tick;
+        }
+        // The round stands still while the quit dialog is open
+        if self.current_screen == CurrentScreen::QuitConfirm {
+            return;
+        }
+        if self.countdown > 0 {
+            self.countdown -= 1;
+            return;
+        }
+        self.apply_replay_inputs();
+        self.round_ticks += 1;
+        self.on_tick();
+        if self.round_ticks.is_multiple_of(20) {
+            self.on_second_update();
+        }
+    }
+
+    pub fn handle_input_events(&mut self, key_event: KeyEvent) {
         match self.current_screen {
             CurrentScreen::Main => match key_event.code {
-                event::KeyCode::Esc => self.exit = true,
+                // A replay has nothing to lose, so it is left right away
+                event::KeyCode::Esc if self.is_replaying() => self.go_back(),
+                event::KeyCode::Esc => self.push_screen(CurrentScreen::QuitConfirm, Some(0)),
+                _ if self.is_replaying() => {}
                 _ => self.handle_movement_input(&key_event),
             },
+            CurrentScreen::QuitConfirm => match key_event.code {
+                event::KeyCode::Esc => self.resume_round(),
+                _ => self.handle_quit_input(&key_event),
+            },
             CurrentScreen::Lost => match key_event.code {
-                event::KeyCode::Esc => self.exit = true,
-                event::KeyCode::Enter => {
-                    self.current_screen = CurrentScreen::Menu;
-                    self.menu_cursor = Some(0);
-                }
-                _ => {}
+                event::KeyCode::Esc => self.go_back(),
+                _ => self.handle_lost_input(&key_event),
             },
             CurrentScreen::Menu => match key_event.code {
                 event::KeyCode::Esc => self.exit = true,
 
                 _ => self.handle_menu_input(&key_event),
             },
+            CurrentScreen::Settings => match key_event.code {
+                event::KeyCode::Esc => self.go_back(),
+                _ => self.handle_settings_input(&key_event),
+            },
+            CurrentScreen::Stats | CurrentScreen::Achievements => match key_event.code {
+                event::KeyCode::Esc | event::KeyCode::Enter => self.go_back(),
+                _ => {}
+            },
+        }
+    }
+
+    /// Opens a screen on top of the current one, going back returns to the current one
+    fn push_screen(&mut self, screen: CurrentScreen, cursor: Option<usize>) {
+        self.screen_stack
+            .push((self.current_screen, self.menu_cursor));
+        self.current_screen = screen;
+        self.menu_cursor = cursor;
+    }
+
+    /// Returns to the screen below the current one, the menu if there is none
+    fn go_back(&mut self) {
+        match self.screen_stack.pop() {
+            Some((screen, cursor)) => {
+                self.current_screen = screen;
+                self.menu_cursor = cursor;
+            }
+            None => self.open_menu(MenuEntry::StartGame),
         }
     }
 
+    /// Returns the actions of the quit dialog in the order they are shown
+    pub fn get_quit_entries(&self) -> Vec<QuitEntry> {
+        let mut entries = vec![QuitEntry::Resume];
+        if self.persistent {
+            entries.push(QuitEntry::SaveAndLeave);
+        }
+        entries.extend([QuitEntry::RecordAndLeave, QuitEntry::DiscardAndLeave]);
+        entries
+    }
+
+    /// Closes the quit dialog, the round continues after a new countdown
+    fn resume_round(&mut self) {
+        self.go_back();
+        self.countdown = COUNTDOWN_TICKS;
+    }
+
+    /// Returns the entries of the main menu in the order they are shown
+    pub fn get_menu_entries(&self) -> Vec<MenuEntry> {
+        let mut entries = vec![];
+        if self.saved_game.is_some() {
+            entries.push(MenuEntry::Continue);
+        }
+        entries.extend([
+            MenuEntry::StartGame,
+            MenuEntry::DailyChallenge,
+            MenuEntry::Settings,
+            MenuEntry::Stats,
+            MenuEntry::Achievements,
+            MenuEntry::Quit,
+        ]);
+        entries
+    }
+
+    /// Shows the menu as the only screen, with the cursor on the given entry
+    fn open_menu(&mut self, selected: MenuEntry) {
+        self.screen_stack.clear();
+        self.current_screen = CurrentScreen::Menu;
+        self.menu_cursor = self
+            .get_menu_entries()
+            .iter()
+            .position(|entry| *entry == selected);
+    }
+
+    /// Moves the cursor one entry up or down in a list with the given amount of entries
+    fn move_cursor(&mut self, up: bool, entry_count: usize) {
+        let cursor = match self.menu_cursor {
+            Some(cursor) if up => Some(cursor.saturating_sub(1)),
+            Some(cursor) => Some((cursor + 1).min(entry_count.saturating_sub(1))),
+            None => Some(0),
+        };
+        if cursor != self.menu_cursor {
+            self.play_sound(SoundEvent::MenuMove);
+        }
+        self.menu_cursor = cursor;
+    }
+
+    fn play_sound(&mut self, sound: SoundEvent) {
+        self.sound.play(sound, &self.settings);
+    }
+
     fn start_game(&mut self) {
+        let field_size = match (self.settings.auto_field_size, self.last_area) {
+            (true, Some(area)) => SnakeGameWidget::get_field_size_for(area),
+            _ => (50, 25),
+        };
+        self.start_round(GameConfig::new(self.settings.game_mode, field_size));
+    }
+
+    /// Starts today's daily challenge, only the first attempt of the day is ranked
+    ///
+    /// The attempt is used up as soon as the round starts, so leaving the round does not give
+    /// another one. While today's attempt is saved it has to be continued instead.
+    fn start_daily_challenge(&mut self) {
+        let date = get_current_date();
+        if self
+            .saved_game
+            .as_ref()
+            .is_some_and(|save| save.config.challenge_date.as_ref() == Some(&date))
+        {
+            self.toasts.push(Toast::new(
+                String::from("Daily challenge"),
+                String::from("Continue today's saved attempt first"),
+            ));
+            return;
+        }
+        self.start_round(GameConfig::daily(&date));
+        if !self.practice_round {
+            let result = self.score_manager.add_daily_attempt(&date, PLAYER_NAME);
+            self.report_save_error("daily attempt", result);
+        }
+    }
+
+    /// Starts a round that plays out the recorded inputs instead of listening to the keyboard,
+    /// its score is not added to the leaderboard
+    pub fn play_replay(&mut self, replay: Replay) {
+        self.start_round(replay.config);
+        self.replay_inputs = Some(replay.inputs.into());
+    }
+
+    /// Saves the current round so it can be continued from the menu,
+    /// or after the game was started again
+    fn save_round(&mut self) -> io::Result<()> {
+        if !self.persistent || self.is_replaying() {
+            return Ok(());
+        }
+        let save = self.get_save_game();
+        save.save()?;
+        self.saved_game = Some(save);
+        Ok(())
+    }
+
+    /// Returns the state of the running round
+    fn get_save_game(&self) -> SaveGame {
+        SaveGame {
+            config: self.config.clone(),
+            snake: self.snake.clone(),
+            direction: self.direction,
+            collectables: self
+                .collectables
+                .iter()
+                .map(|spawned| SavedCollectable {
+                    collectable_type: spawned.collectable_type.to_string(),
+                    positions: spawned.collectable.get_positions(),
+                    despawn_timer: spawned.despawn_timer,
+                })
+                .collect(),
+            effects: self
+                .effects
+                .iter()
+                .map(|effect| SavedEffect {
+                    effect_type: effect.effect_type.to_string(),
+                    duration: effect.duration,
+                    remaining_time: effect.remaining_time,
+                })
+                .collect(),
+            hazards: self.hazards.clone(),
+            arena: self.arena.clone(),
+            game_speed: self.game_speed,
+            move_counter: self.move_counter,
+            round_time: self.round_time,
+            round_ticks: self.round_ticks,
+            random_item_timer: self.random_item_timer,
+            scoring: self.scoring.clone(),
+            stats: self.stats.clone(),
+            recorded_inputs: self.recorded_inputs.clone(),
+            rng: self.rng.clone(),
+            practice_round: self.practice_round,
+        }
+    }
+
+    /// Continues the round that was saved on quit, its score is marked on the leaderboard
+    fn continue_round(&mut self) {
+        let Some(save) = self.saved_game.take() else {
+            return;
+        };
+        if self.persistent {
+            SaveGame::delete();
+        }
+        self.start_round(save.config);
+        self.snake = save.snake;
+        self.direction = save.direction;
+        self.collectables = save
+            .collectables
+            .into_iter()
+            .filter_map(|saved| {
+                let definition = self.registry.get(&saved.collectable_type)?;
+                let (x, y) = *saved.positions.first()?;
+                let mut collectable = definition.create(x, y);
+                collectable.set_positions(&saved.positions);
+                Some(SpawnedCollectable::new(
+                    definition.id,
+                    collectable,
+                    saved.despawn_timer,
+                ))
+            })
+            .collect();
+        self.effects = save
+            .effects
+            .into_iter()
+            .filter_map(|saved| {
+                let definition = self.registry.get(&saved.effect_type)?;
+                Some(ActiveEffect {
+                    effect_type: definition.id,
+                    duration: saved.duration,
+                    remaining_time: saved.remaining_time,
+                })
+            })
+            .collect();
+        self.hazards = save.hazards;
+        self.arena = save.arena;
+        self.game_speed = save.game_speed;
+        self.move_counter = save.move_counter;
+        self.round_time = save.round_time;
+        self.round_ticks = save.round_ticks;
+        self.random_item_timer = save.random_item_timer;
+        self.scoring = save.scoring;
+        self.stats = save.stats;
+        self.recorded_inputs = save.recorded_inputs;
+        self.rng = save.rng;
+        // Starting the round again counted it as an attempt, but the saved round is the attempt
+        self.practice_round = save.practice_round;
+        self.spawn_table = SpawnTable::new(&self.config, self.get_level(), &self.registry);
+        self.resumed = true;
+    }
+
+    /// Returns whether the round was saved on quit and continued later
+    pub fn is_resumed(&self) -> bool {
+        self.resumed
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay_inputs.is_some()
+    }
+
+    /// Returns the recording of the current round
+    pub fn get_replay(&self) -> Replay {
+        Replay {
+            config: self.config.clone(),
+            inputs: self.recorded_inputs.clone(),
+        }
+    }
+
+    fn start_round(&mut self, config: GameConfig) {
+        // Leaving the round goes back to the menu it was started from
+        if self.current_screen == CurrentScreen::Menu {
+            self.screen_stack = vec![(CurrentScreen::Menu, self.menu_cursor)];
+        }
         self.current_screen = CurrentScreen::Main;
-        self.snake = vec![
-            (23.0, 20.0),
-            (22.0, 20.0),
-            (21.0, 20.0),
-            (20.0, 20.0),
-            (19.0, 20.0),
-        ];
+        self.field_size = config.field_size;
+        self.rng = ChaCha12Rng::seed_from_u64(config.seed);
+        self.config = config;
+        self.stats = RunStats::default();
+        self.scoring = Scoring::default();
+        self.round_ticks = 0;
+        self.recorded_inputs = vec![];
+        self.replay_inputs = None;
+        self.resumed = false;
+        self.practice_round = self
+            .config
+            .challenge_date
+            .as_ref()
+            .is_some_and(|date| self.score_manager.has_daily_attempt(date, PLAYER_NAME));
+        self.notice = None;
+        self.blocked = false;
+        let head_x = (self.field_size.0 / 2 + 2) as f64;
+        let head_y = (self.field_size.1 / 2) as f64;
+        self.snake = (0..5).map(|i| (head_x - i as f64, head_y)).collect();
         self.direction = Direction::Right;
         self.menu_cursor = None;
         self.collectables = vec![];
+        self.effects = vec![];
+        self.hazards = vec![];
+        self.arena = (self.config.game_mode == GameMode::ShrinkingArena)
+            .then(|| Arena::new(self.field_size));
+        self.animations = vec![];
+        self.countdown = COUNTDOWN_TICKS;
         self.game_speed = 0;
-        self.spawn_item(CollectableType::Apple);
+        self.move_counter = 0;
         self.round_time = 0;
+        self.spawn_table = SpawnTable::new(&self.config, self.get_level(), &self.registry);
+        self.random_item_timer = self.spawn_table.get_next_spawn_time(&mut self.rng);
+        if self.config.game_mode == GameMode::Survival {
+            for _ in 0..START_HAZARDS {
+                self.spawn_hazard();
+            }
+        }
+        self.spawn_item(APPLE);
+    }
+
+    /// Applies the recorded inputs that are due before the next round tick
+    fn apply_replay_inputs(&mut self) {
+        while let Some(input) = self.take_due_replay_input() {
+            let previous_direction = self.direction;
+            self.direction = input.direction;
+            self.on_direction_changed(previous_direction);
+        }
+    }
+
+    fn take_due_replay_input(&mut self) -> Option<ReplayInput> {
+        let replay_inputs = self.replay_inputs.as_mut()?;
+        if replay_inputs.front()?.tick > self.round_ticks {
+            return None;
+        }
+        replay_inputs.pop_front()
     }
 
     fn handle_menu_input(&mut self, key_event: &KeyEvent) {
+        let entries = self.get_menu_entries();
         match key_event.code {
-            event::KeyCode::Up => {
-                if let Some(cursor) = self.menu_cursor {
-                    self.menu_cursor = Some(if cursor == 0 { 0 } else { cursor - 1 });
-                } else {
-                    self.menu_cursor = Some(0);
+            event::KeyCode::Up => self.move_cursor(true, entries.len()),
+            event::KeyCode::Down => self.move_cursor(false, entries.len()),
+            event::KeyCode::PageUp => {
+                self.highscore_scroll = self.highscore_scroll.saturating_sub(1);
+            }
+            event::KeyCode::PageDown => self.highscore_scroll += 1,
+            event::KeyCode::Enter => {
+                if let Some(entry) = self.menu_cursor.and_then(|cursor| entries.get(cursor)) {
+                    self.play_sound(SoundEvent::MenuSelect);
+                    match entry {
+                        MenuEntry::Continue => self.continue_round(),
+                        MenuEntry::StartGame => self.start_game(),
+                        MenuEntry::DailyChallenge => self.start_daily_challenge(),
+                        MenuEntry::Settings => {
+                            self.push_screen(CurrentScreen::Settings, Some(0));
+                        }
+                        MenuEntry::Stats => self.push_screen(CurrentScreen::Stats, None),
+                        MenuEntry::Achievements => {
+                            self.push_screen(CurrentScreen::Achievements, None);
+                        }
+                        MenuEntry::Quit => self.exit = true,
+                    }
                 }
             }
-            event::KeyCode::Down => {
-                if let Some(cursor) = self.menu_cursor {
-                    self.menu_cursor = Some(if cursor == 1 { 1 } else { cursor + 1 });
-                } else {
-                    self.menu_cursor = Some(0);
+            _ => {}
+        }
+    }
+
+    fn handle_lost_input(&mut self, key_event: &KeyEvent) {
+        if self.is_dying() {
+            // Enter skips the death animation
+            if key_event.code == event::KeyCode::Enter {
+                self.animations.clear();
+            }
+            return;
+        }
+        match key_event.code {
+            event::KeyCode::Up | event::KeyCode::Left => {
+                self.move_cursor(true, LostEntry::ALL.len());
+            }
+            event::KeyCode::Down | event::KeyCode::Right => {
+                self.move_cursor(false, LostEntry::ALL.len());
+            }
+            event::KeyCode::Enter => {
+                match self
+                    .menu_cursor
+                    .and_then(|cursor| LostEntry::ALL.get(cursor))
+                {
+                    Some(LostEntry::Retry) => self.start_round(self.config.for_retry()),
+                    Some(LostEntry::SaveReplay) => {
+                        self.notice = Some(match self.get_replay().save() {
+                            Ok(path) => {
+                                let file_name =
+                                    Path::new(&path).file_name().map_or(path.clone(), |name| {
+                                        name.to_string_lossy().to_string()
+                                    });
+                                format!("Replay saved as {}", file_name)
+                            }
+                            Err(err) => format!("Could not save the replay: {}", err),
+                        });
+                    }
+                    Some(LostEntry::Menu) => self.go_back(),
+                    None => {}
                 }
             }
+            _ => {}
+        }
+    }
+
+    fn handle_quit_input(&mut self, key_event: &KeyEvent) {
+        let entries = self.get_quit_entries();
+        match key_event.code {
+            event::KeyCode::Up | event::KeyCode::Left => self.move_cursor(true, entries.len()),
+            event::KeyCode::Down | event::KeyCode::Right => {
+                self.move_cursor(false, entries.len());
+            }
             event::KeyCode::Enter => {
-                if let Some(cursor) = self.menu_cursor {
-                    match cursor {
-                        0 => self.start_game(),
-                        1 => self.exit = true,
-                        _ => {}
+                match self.menu_cursor.and_then(|cursor| entries.get(cursor)) {
+                    Some(QuitEntry::Resume) => self.resume_round(),
+                    // The dialog stays open if the round could not be saved, so it is not lost
+                    Some(QuitEntry::SaveAndLeave) => {
+                        let result = self.save_round();
+                        if result.is_ok() {
+                            self.open_menu(MenuEntry::Continue);
+                        }
+                        self.report_save_error("round", result);
                     }
+                    Some(QuitEntry::RecordAndLeave) => {
+                        self.end_round();
+                        self.open_menu(MenuEntry::StartGame);
+                    }
+                    Some(QuitEntry::DiscardAndLeave) => self.open_menu(MenuEntry::StartGame),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_settings_input(&mut self, key_event: &KeyEvent) {
+        match key_event.code {
+            event::KeyCode::Up => self.move_cursor(true, SettingsEntry::ALL.len()),
+            event::KeyCode::Down => self.move_cursor(false, SettingsEntry::ALL.len()),
+            event::KeyCode::Left | event::KeyCode::Right | event::KeyCode::Enter => {
+                let forward = key_event.code != event::KeyCode::Left;
+                if let Some(entry) = self
+                    .menu_cursor
+                    .and_then(|cursor| SettingsEntry::ALL.get(cursor))
+                {
+                    self.change_setting(*entry, forward);
                 }
             }
             _ => {}
         }
     }
 
+    /// Switches the setting to its next or previous value and saves the settings
+    pub fn change_setting(&mut self, entry: SettingsEntry, forward: bool) {
+        match entry {
+            SettingsEntry::FieldSize => {
+                self.settings.auto_field_size = !self.settings.auto_field_size;
+            }
+            SettingsEntry::Theme => {
+                let themes = Theme::get_available_themes();
+                let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+                self.settings.theme =
+                    get_cycled(&names, self.settings.theme.as_str(), forward).to_string();
+                self.theme = Theme::load(&self.settings.theme);
+            }
+            SettingsEntry::RenderStyle => {
+                self.settings.render_style =
+                    get_cycled(&RenderStyle::ALL, self.settings.render_style, forward);
+            }
+            SettingsEntry::SnakeSkin => {
+                self.settings.snake_skin =
+                    get_cycled(&SnakeSkin::ALL, self.settings.snake_skin, forward);
+            }
+            SettingsEntry::GameMode => {
+                self.settings.game_mode =
+                    get_cycled(&GameMode::SELECTABLE, self.settings.game_mode, forward);
+            }
+            SettingsEntry::Sound => self.settings.sound_muted = !self.settings.sound_muted,
+            SettingsEntry::Volume => self.settings.volume = self.settings.volume.changed(forward),
+        }
+        // Also lets the player hear the new volume
+        self.play_sound(SoundEvent::MenuSelect);
+        let result = self.settings.save();
+        self.report_save_error("settings", result);
+    }
+
+    /// Returns the current value of a setting as it is shown in the settings screen
+    pub fn get_setting_value(&self, entry: SettingsEntry) -> String {
+        match entry {
+            SettingsEntry::FieldSize => {
+                if self.settings.auto_field_size {
+                    String::from("Auto")
+                } else {
+                    String::from("50 x 25")
+                }
+            }
+            SettingsEntry::Theme => {
+                if is_no_color_set() {
+                    format!("{} (NO_COLOR)", self.theme.name)
+                } else {
+                    self.theme.name.clone()
+                }
+            }
+            SettingsEntry::RenderStyle => self.settings.render_style.get_label().to_string(),
+            SettingsEntry::SnakeSkin => self.settings.snake_skin.get_label().to_string(),
+            SettingsEntry::GameMode => self.settings.game_mode.get_label().to_string(),
+            SettingsEntry::Sound => {
+                if self.settings.sound_muted {
+                    String::from("Off")
+                } else {
+                    String::from("On")
+                }
+            }
+            SettingsEntry::Volume => {
+                let step = self.settings.volume.get_step() as usize;
+                format!(
+                    "{}{}",
+                    "█".repeat(step),
+                    "░".repeat(Volume::MAX as usize - step)
+                )
+            }
+        }
+    }
+
     fn handle_movement_input(&mut self, key_event: &KeyEvent) {
         if !self.blocked {
+            let previous_direction = self.direction;
             match key_event.code {
                 event::KeyCode::Up => match self.direction {
                     Direction::Down => (),
@@ -206,75 +907,543 @@ impl App {
                 },
                 _ => {}
             }
+            if self.direction != previous_direction {
+                self.on_direction_changed(previous_direction);
+            }
             self.blocked = true;
         }
     }
 
-    fn on_tick(&mut self, counter: &mut u32) {
+    /// Records the new direction for the replay and reports the turn
+    fn on_direction_changed(&mut self, previous_direction: Direction) {
+        self.recorded_inputs.push(ReplayInput {
+            tick: self.round_ticks,
+            direction: self.direction,
+        });
+        if let Some(turn) = previous_direction.get_turn_to(self.direction) {
+            self.emit(GameEvent::Turned(turn));
+        }
+    }
+
+    /// Lets the stats and achievements react to something that happened in the round
+    pub fn emit(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::Collected(_) => self.play_sound(SoundEvent::Collect),
+            GameEvent::Died(_) => self.play_sound(SoundEvent::Died),
+            _ => {}
+        }
+        let level = self.get_level();
+        self.stats.on_event(&event, level, &self.registry);
+        let score_event =
+            self.scoring
+                .on_event(&event, &self.registry, self.game_speed, self.round_ticks);
+        if let Some(score_event) = score_event
+            && self.config.game_mode.is_scored_by_points()
+        {
+            self.animations.push(Animation::new(
+                AnimationKind::ScorePopup(score_event.points),
+                self.snake[0],
+                SCORE_POPUP_TICKS,
+            ));
+        }
+        if !self.is_replaying() {
+            self.check_achievements(&event);
+        }
+    }
+
+    fn check_achievements(&mut self, event: &GameEvent) {
+        for achievement in &ACHIEVEMENTS {
+            if !self.achievements.is_unlocked(achievement.id)
+                && achievement.condition.is_met(event, self)
+            {
+                let result = self.achievements.unlock(achievement.id);
+                self.report_save_error("achievements", result);
+                self.toasts.push(Toast::new(
+                    String::from("Achievement unlocked"),
+                    achievement.name.to_string(),
+                ));
+            }
+        }
+    }
+
+    /// Returns the amount of game ticks between two snake movements
+    pub fn get_move_interval(&self) -> u32 {
+        (3 - self.game_speed).max(1) as u32
+    }
+
+    fn on_tick(&mut self) {
         match self.current_screen {
             CurrentScreen::Menu => {}
             CurrentScreen::Lost => {}
+            CurrentScreen::Settings => {}
+            CurrentScreen::Stats => {}
+            CurrentScreen::Achievements => {}
+            CurrentScreen::QuitConfirm => {}
             CurrentScreen::Main => {
-                if *counter == self.game_speed || *counter == 0 {
+                self.move_counter += 1;
+                if self.move_counter >= self.get_move_interval() {
                     self.game_update();
-                    *counter = 3;
+                    self.move_counter = 0;
                 }
             }
         }
     }
     fn on_second_update(&mut self) {
-        match self.current_screen {
-            CurrentScreen::Main => {
-                // Spawn Item
-                if self.random_item_timer <= 0 {
-                    self.spawn_item(CollectableType::from_random_special());
-                    self.random_item_timer = rand::random_range(10..20);
-                } else {
-                    self.random_item_timer -= 1;
+        if let CurrentScreen::Main = self.current_screen {
+            // Spawn Item
+            if self.random_item_timer == 0 {
+                let roll = self.rng.random();
+                if let Some(collectable_type) = self.spawn_table.choose(self, roll) {
+                    self.spawn_item(collectable_type);
                 }
+                self.random_item_timer = self.spawn_table.get_next_spawn_time(&mut self.rng);
+            } else {
+                self.random_item_timer -= 1;
+            }
 
-                self.round_time += 1;
-                for collectable in &mut self.collectables {
-                    collectable.on_second_update();
+            self.round_time += 1;
+            self.emit(GameEvent::Second);
+            if self.round_time.is_multiple_of(60) {
+                self.spawn_table = SpawnTable::new(&self.config, self.get_level(), &self.registry);
+                self.emit(GameEvent::LevelUp(self.get_level()));
+                if self.config.game_mode == GameMode::Survival && self.hazards.len() < MAX_HAZARDS {
+                    self.spawn_hazard();
                 }
             }
-            _ => {}
+            if self
+                .arena
+                .as_mut()
+                .is_some_and(|arena| arena.on_second_update())
+            {
+                self.on_arena_shrunk();
+                if !matches!(self.current_screen, CurrentScreen::Main) {
+                    return;
+                }
+            }
+            let time_limit = self.config.game_mode.get_time_limit();
+            if time_limit.is_some_and(|limit| self.round_time >= limit) {
+                self.notice = Some(String::from("Time's up!"));
+                self.end_round();
+                return;
+            }
+            self.collectables
+                .retain_mut(|spawned| !spawned.on_second_update());
+            self.update_effects();
+        }
+    }
+
+    /// Returns the effect that the item grants, if it grants one
+    fn get_effect_definition(&self, effect_type: CollectableId) -> Option<EffectDefinition> {
+        self.registry.get(effect_type)?.effect
+    }
+
+    /// Activates the effect of the item for the given amount of seconds,
+    /// following the stack rule of the effect
+    pub fn add_effect(&mut self, effect_type: CollectableId, duration: u32) {
+        let Some(definition) = self.get_effect_definition(effect_type) else {
+            return;
+        };
+        let active = self
+            .effects
+            .iter()
+            .position(|effect| effect.effect_type == effect_type);
+        match (definition.stack_rule, active) {
+            (StackRule::Refresh, Some(index)) => {
+                let effect = &mut self.effects[index];
+                effect.duration = duration;
+                effect.remaining_time = duration;
+            }
+            (StackRule::Extend, Some(index)) => {
+                let effect = &mut self.effects[index];
+                effect.duration = effect.remaining_time + duration;
+                effect.remaining_time += duration;
+            }
+            (StackRule::Stack(max), Some(_)) => {
+                let count = self
+                    .effects
+                    .iter()
+                    .filter(|effect| effect.effect_type == effect_type)
+                    .count();
+                if count < max {
+                    definition.apply(self);
+                    self.effects.push(ActiveEffect::new(effect_type, duration));
+                }
+            }
+            (_, None) => {
+                definition.apply(self);
+                self.effects.push(ActiveEffect::new(effect_type, duration));
+            }
+        }
+    }
+
+    /// Returns true while the death animation of the snake is running
+    pub fn is_dying(&self) -> bool {
+        self.animations
+            .iter()
+            .any(|animation| matches!(animation.kind, AnimationKind::Death))
+    }
+
+    pub fn has_effect(&self, effect_type: CollectableId) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.effect_type == effect_type)
+    }
+
+    /// Ends one active instance of the effect early
+    ///
+    /// Returns true if an instance was active
+    pub fn consume_effect(&mut self, effect_type: CollectableId) -> bool {
+        match self
+            .effects
+            .iter()
+            .position(|effect| effect.effect_type == effect_type)
+        {
+            Some(index) => {
+                let effect = self.effects.remove(index);
+                self.expire_effect(&effect);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expire_effect(&mut self, effect: &ActiveEffect) {
+        if let Some(definition) = self.get_effect_definition(effect.effect_type) {
+            definition.expire(self);
+        }
+    }
+
+    /// Counts down all active effects and expires the ones that ran out
+    fn update_effects(&mut self) {
+        let mut i = 0;
+        while i < self.effects.len() {
+            let effect = &mut self.effects[i];
+            effect.remaining_time = effect.remaining_time.saturating_sub(1);
+            if effect.remaining_time == 0 {
+                let effect = self.effects.remove(i);
+                self.expire_effect(&effect);
+                self.emit(GameEvent::EffectExpired(effect.effect_type));
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Places a new item on a free cell, nothing is spawned if the field is full
+    pub fn spawn_item(&mut self, collectable_type: CollectableId) {
+        let Some((x, y)) = self.get_free_position() else {
+            return;
+        };
+        let Some(definition) = self.registry.get(collectable_type) else {
+            return;
+        };
+        let mut new_collectable = definition.create(x, y);
+        new_collectable.on_spawn(self);
+        let despawn_timer = self
+            .spawn_table
+            .get_rule(collectable_type)
+            .and_then(|rule| rule.despawn_after);
+        self.collectables.push(SpawnedCollectable::new(
+            collectable_type,
+            new_collectable,
+            despawn_timer,
+        ));
+    }
+
+    /// Returns a random position in the play area that is not covered by the snake,
+    /// a hazard or another item, `None` if there is none left
+    pub fn get_free_position(&mut self) -> Option<(f64, f64)> {
+        let free_positions = self.get_free_positions();
+        self.choose_position(&free_positions)
+    }
+
+    /// Returns every position in the play area that items and hazards can be placed on
+    pub fn get_free_positions(&self) -> Vec<(f64, f64)> {
+        let (width, height) = self.field_size;
+        let mut positions = vec![];
+        for x in 1..width.saturating_sub(1) {
+            for y in 1..height.saturating_sub(1) {
+                let position = (x as f64, y as f64);
+                let is_occupied = !self.is_in_play_area(position)
+                    || self.snake.contains(&position)
+                    || self
+                        .hazards
+                        .iter()
+                        .any(|hazard| hazard.position == position)
+                    || self
+                        .collectables
+                        .iter()
+                        .any(|spawned| spawned.collectable.get_positions().contains(&position));
+                if !is_occupied {
+                    positions.push(position);
+                }
+            }
+        }
+        positions
+    }
+
+    /// Picks one of the positions at random
+    pub fn choose_position(&mut self, positions: &[(f64, f64)]) -> Option<(f64, f64)> {
+        if positions.is_empty() {
+            return None;
+        }
+        Some(positions[self.rng.random_range(0..positions.len())])
+    }
+
+    /// Returns whether the position is inside the walls, which may have moved in
+    pub fn is_in_play_area(&self, position: (f64, f64)) -> bool {
+        is_inside(position, self.get_arena_inset(), self.field_size)
+    }
+
+    fn get_arena_inset(&self) -> u32 {
+        self.arena.as_ref().map_or(0, |arena| arena.inset)
+    }
+
+    /// Moves the items that were closed off back into the play area,
+    /// the round ends if the head got caught by the walls
+    fn on_arena_shrunk(&mut self) {
+        let collectables = std::mem::take(&mut self.collectables);
+        let (outside, inside): (Vec<_>, Vec<_>) = collectables.into_iter().partition(|spawned| {
+            spawned
+                .collectable
+                .get_positions()
+                .iter()
+                .any(|position| !self.is_in_play_area(*position))
+        });
+        self.collectables = inside;
+        for spawned in outside {
+            self.spawn_item(spawned.collectable_type);
+        }
+        if !self.is_in_play_area(self.snake[0]) {
+            self.game_over(DeathCause::Wall);
         }
     }
 
-    pub fn spawn_item(&mut self, collectable_type: CollectableType) {
-        let x: f64 = random_range(1..self.field_size.0 - 1) as f64;
-        let y: f64 = random_range(1..self.field_size.1 - 1) as f64;
-        let new_collectable = AnyCollectable::new(x, y, collectable_type.clone());
-        if self.snake.contains(&new_collectable.get_position()) {
-            self.spawn_item(collectable_type);
+    /// Places a hazard on a free cell that is not right in front of the snake
+    fn spawn_hazard(&mut self) {
+        let head = self.snake[0];
+        let mut free_positions = self.get_free_positions();
+        free_positions.retain(|position| {
+            (position.0 - head.0).abs() + (position.1 - head.1).abs() * 2.0 > 10.0
+        });
+        let Some(position) = self.choose_position(&free_positions) else {
+            return;
+        };
+        let direction = Direction::ALL[self.rng.random_range(0..Direction::ALL.len())];
+        self.hazards.push(Hazard::new(position, direction));
+    }
+
+    fn move_hazards(&mut self) {
+        let move_interval = get_hazard_move_interval(self.round_time);
+        for hazard in &mut self.hazards {
+            hazard.on_game_update(move_interval, self.field_size, &mut self.rng);
+        }
+    }
+
+    pub fn game_over(&mut self, cause: DeathCause) {
+        self.current_screen = CurrentScreen::Lost;
+        self.emit(GameEvent::Died(cause));
+        self.animations.push(Animation::new(
+            AnimationKind::Death,
+            self.snake[0],
+            DEATH_TICKS,
+        ));
+        self.end_round();
+    }
+
+    /// Shows the game over screen, puts the score on its leaderboard and adds the round
+    /// to the lifetime stats
+    fn end_round(&mut self) {
+        self.current_screen = CurrentScreen::Lost;
+        self.menu_cursor = Some(0);
+        if self.is_replaying() {
+            return;
+        }
+        let game_mode = self.config.game_mode;
+        let score = self.get_score();
+        // Without reaching the goal of an Apple Rush there is no time to rank
+        let finished = game_mode
+            .get_apple_goal()
+            .is_none_or(|goal| self.stats.apples_eaten >= goal);
+        if !finished {
+            self.notice = Some(format!(
+                "{} of {} apples eaten, no time was set",
+                self.stats.apples_eaten,
+                game_mode.get_apple_goal().unwrap_or_default()
+            ));
+        } else if self.practice_round {
+            self.notice = Some(String::from(
+                "Practice run, today's attempt was already used",
+            ));
+        } else if let Some(date) = &self.config.challenge_date {
+            let score = self.create_score(score);
+            match self.score_manager.add_daily_score(date, score) {
+                Ok(rank) => self.stats.rank = Some(rank),
+                Err(err) => self.notice = Some(format!("Could not save the score: {}", err)),
+            }
         } else {
-            self.collectables.push(new_collectable);
+            self.stats.personal_best = self
+                .score_manager
+                .get_best_score(game_mode, PLAYER_NAME)
+                .is_none_or(|best| {
+                    if game_mode.is_lower_score_better() {
+                        score < best
+                    } else {
+                        score > best
+                    }
+                });
+            let score = self.create_score(score);
+            match self.score_manager.add_score(score) {
+                Ok(rank) => self.stats.rank = Some(rank),
+                Err(err) => self.notice = Some(format!("Could not save the score: {}", err)),
+            }
+        }
+        let result = self.stats_store.record_run(
+            PLAYER_NAME,
+            &self.stats,
+            game_mode,
+            finished.then_some(score),
+            self.round_time,
+        );
+        self.report_save_error("statistics", result);
+    }
+
+    /// Tells the player in a toast that some of their data could not be written,
+    /// the game goes on with the data it has in memory
+    fn report_save_error(&mut self, what: &str, result: io::Result<()>) {
+        if let Err(err) = result {
+            self.toasts.push(Toast::new(
+                format!("Could not save the {}", what),
+                err.to_string(),
+            ));
+        }
+    }
+
+    fn create_score(&self, score: i32) -> Score {
+        Score {
+            player_name: String::from(PLAYER_NAME),
+            score,
+            game_mode: self.config.game_mode,
+            resumed: self.resumed,
         }
     }
 
     fn game_update(&mut self) {
+        self.stats.max_speed = self.stats.max_speed.max(self.game_speed);
         self.check_collectable_collision();
+        let apple_goal = self.config.game_mode.get_apple_goal();
+        if matches!(self.current_screen, CurrentScreen::Main)
+            && apple_goal.is_some_and(|goal| self.stats.apples_eaten >= goal)
+        {
+            self.notice = Some(String::from("All apples eaten!"));
+            self.end_round();
+        }
+        if !matches!(self.current_screen, CurrentScreen::Main) {
+            return;
+        }
+        let old_head = self.snake[0];
+        let old_hazard_positions: Vec<(f64, f64)> =
+            self.hazards.iter().map(|hazard| hazard.position).collect();
+        self.move_hazards();
         self.update_snake_position();
+        if self.has_effect(MAGNET) {
+            self.pull_apples();
+        }
 
         // Items
-        if self.has_snake_collision() {
-            self.current_screen = CurrentScreen::Lost;
-            self.score_manager
-                .add_score(String::from("you"), self.get_score());
+        let collision = self.get_collision().or_else(|| {
+            self.has_swapped_with_hazard(old_head, &old_hazard_positions)
+                .then_some(DeathCause::Obstacle)
+        });
+        if let Some(cause) = collision {
+            self.absorb_collision(cause);
+        }
+    }
+
+    /// Lets open walls or the shield save the snake from what it ran into,
+    /// the round ends otherwise
+    ///
+    /// Returns true if the snake survived
+    fn absorb_collision(&mut self, cause: DeathCause) -> bool {
+        let open_wall = cause == DeathCause::Wall && self.config.modifiers.open_walls;
+        if !open_wall && !self.consume_effect(SHIELD) {
+            self.game_over(cause);
+            return false;
+        }
+        match cause {
+            DeathCause::Wall => self.wrap_snake_head(),
+            DeathCause::Obstacle => {
+                // The shield breaks the hazard it ran into
+                let snake = &self.snake;
+                self.hazards
+                    .retain(|hazard| !snake.contains(&hazard.position));
+            }
+            DeathCause::Tail => {
+                // The snake still heads into its body, it passes through for a moment
+                // so the player has time to turn away
+                self.add_effect(GHOST, SHIELD_GRACE_SECONDS);
+            }
+        }
+        true
+    }
+
+    /// Moves every apple near the head one cell closer to it
+    fn pull_apples(&mut self) {
+        let head = self.snake[0];
+        let is_near = |(x, y): (f64, f64)| (x - head.0).abs() + (y - head.1).abs() * 2.0 <= 8.0;
+        for spawned in &mut self.collectables {
+            if is_near(spawned.collectable.get_position()) {
+                spawned.collectable.move_towards(head);
+            }
+        }
+    }
+
+    /// Moves the head back into the field on the opposite side of the wall it hit
+    fn wrap_snake_head(&mut self) {
+        let inset = self.get_arena_inset() as f64;
+        // A cell is half as high as it is wide
+        let (inset_x, inset_y) = (inset, inset * 0.5);
+        let (width, height) = (
+            self.field_size.0 as f64 - inset_x,
+            self.field_size.1 as f64 - inset_y,
+        );
+        let head = &mut self.snake[0];
+        if head.0 > width {
+            head.0 = inset_x + 1.0;
+        } else if head.0 <= inset_x {
+            head.0 = width;
+        }
+        if head.1 >= height {
+            head.1 = inset_y;
+        } else if head.1 < inset_y {
+            head.1 = height - 0.5;
         }
     }
 
     fn check_collectable_collision(&mut self) {
         let mut i = 0;
         while i < self.collectables.len() {
-            let mut collectable = self.collectables.remove(i);
+            let mut spawned = self.collectables.remove(i);
+            let collectable = &mut spawned.collectable;
 
             let mut should_remove = false;
-            if self.snake[0] == collectable.get_position() {
-                if collectable.on_collect(self) {
-                    should_remove = true;
-                }
+            let position = self.snake[0];
+            if collectable.get_positions().contains(&position) {
+                let result = collectable.on_collect(self);
+                self.emit(GameEvent::Collected(spawned.collectable_type));
+                self.animations.push(Animation::new(
+                    AnimationKind::Flash(spawned.collectable_type),
+                    position,
+                    FLASH_TICKS,
+                ));
+                should_remove = match result {
+                    CollectResult::Keep => false,
+                    CollectResult::Remove => true,
+                    CollectResult::Deadly(cause) => {
+                        self.absorb_collision(cause);
+                        true
+                    }
+                };
             }
 
             if !should_remove && collectable.on_game_update(self) {
@@ -282,7 +1451,7 @@ impl App {
             }
 
             if !should_remove {
-                self.collectables.insert(i, collectable);
+                self.collectables.insert(i, spawned);
                 i += 1;
             }
         }
@@ -301,31 +1470,409 @@ impl App {
 
     fn update_snake_position(&mut self) {
         let (head_x, head_y) = self.snake[0];
-        let new_head = match self.direction {
-            Direction::Up => (head_x, head_y + 0.5),
-            Direction::Down => (head_x, head_y - 0.5),
-            Direction::Left => (head_x - 1.0, head_y),
-            Direction::Right => (head_x + 1.0, head_y),
-        };
+        let (dx, dy) = self.direction.get_vector();
+        let new_head = (head_x + dx, head_y + dy);
         self.snake.insert(0, new_head);
         self.snake.pop();
         self.blocked = false;
     }
-    fn has_snake_collision(&self) -> bool {
+    /// Returns whether the head and a hazard moved past each other into the cell the other
+    /// one left, they never share a cell then but still ran into each other
+    fn has_swapped_with_hazard(
+        &self,
+        old_head: (f64, f64),
+        old_hazard_positions: &[(f64, f64)],
+    ) -> bool {
         let head = self.snake[0];
-        for segment in &self.snake[1..] {
-            if head == *segment {
-                return true;
-            }
+        self.hazards
+            .iter()
+            .zip(old_hazard_positions)
+            .any(|(hazard, old_position)| hazard.position == old_head && *old_position == head)
+    }
+
+    /// Returns what the head ran into, if anything
+    fn get_collision(&self) -> Option<DeathCause> {
+        let head = self.snake[0];
+        if !self.has_effect(GHOST) && self.snake[1..].contains(&head) {
+            return Some(DeathCause::Tail);
+        }
+        if !self.is_in_play_area(head) {
+            return Some(DeathCause::Wall);
         }
-        if head.0 > self.field_size.0 as f64
-            || head.0 <= 0.0
-            || head.1 >= self.field_size.1 as f64
-            || head.1 < 0.0
+        // Hazards also kill when they run into the body
+        if self
+            .hazards
+            .iter()
+            .any(|hazard| self.snake.contains(&hazard.position))
         {
-            return true;
+            return Some(DeathCause::Obstacle);
+        }
+        None
+    }
+}
+
+/// Switches the terminal to the game, restoring it again if that fails half way
+fn init_terminal() -> io::Result<DefaultTerminal> {
+    ratatui::try_init().map_err(|err| {
+        ratatui::restore();
+        io::Error::new(
+            err.kind(),
+            format!("could not set up the terminal: {}", err),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{
+        collectables::{GOLDEN_APPLE, SLOW, SPEED},
+        enums::GameMode,
+    };
+    use crossterm::event::KeyCode;
+
+    /// Creates a game that does not touch the score file
+    fn create_app() -> App {
+        App::with_score_manager(ScoreManager::in_memory())
+    }
+
+    fn play_ticks(app: &mut App, ticks: u64) {
+        for _ in 0..ticks {
+            app.on_game_tick();
+        }
+    }
+
+    fn get_item_positions(app: &App) -> Vec<(f64, f64)> {
+        app.collectables
+            .iter()
+            .flat_map(|spawned| spawned.collectable.get_positions())
+            .collect()
+    }
+
+    #[test]
+    fn stacking_effects_stop_at_their_limit() {
+        let mut app = create_app();
+        for _ in 0..3 {
+            app.add_effect(SPEED, 10);
+        }
+        assert_eq!(app.effects.len(), 2);
+        assert_eq!(app.game_speed, 2);
+    }
+
+    #[test]
+    fn extending_effects_add_their_duration() {
+        let mut app = create_app();
+        app.add_effect(SLOW, 10);
+        for _ in 0..3 {
+            app.update_effects();
+        }
+        app.add_effect(SLOW, 5);
+        assert_eq!(app.effects.len(), 1);
+        assert_eq!(app.effects[0].remaining_time, 12);
+        assert_eq!(app.effects[0].duration, 12);
+        assert_eq!(app.game_speed, -1);
+    }
+
+    #[test]
+    fn refreshing_effects_start_over() {
+        let mut app = create_app();
+        app.add_effect(GHOST, 8);
+        for _ in 0..5 {
+            app.update_effects();
+        }
+        app.add_effect(GHOST, 6);
+        assert_eq!(app.effects.len(), 1);
+        assert_eq!(app.effects[0].remaining_time, 6);
+        assert_eq!(app.effects[0].duration, 6);
+    }
+
+    #[test]
+    fn expired_effects_are_undone() {
+        let mut app = create_app();
+        app.add_effect(SPEED, 2);
+        app.add_effect(SLOW, 4);
+        for _ in 0..2 {
+            app.update_effects();
         }
+        assert_eq!(app.game_speed, -1);
+        for _ in 0..2 {
+            app.update_effects();
+        }
+        assert!(app.effects.is_empty());
+        assert_eq!(app.game_speed, 0);
+    }
+
+    #[test]
+    fn replay_plays_out_the_same_round() {
+        let mut app = create_app();
+        let mut config = GameConfig::new(GameMode::Classic, (50, 25));
+        config.seed = 7;
+        app.start_round(config);
+        for key in [KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right] {
+            play_ticks(&mut app, 30);
+            app.handle_input_events(KeyEvent::from(key));
+        }
+        play_ticks(&mut app, 300);
+        let replay = app.get_replay();
+        assert!(!replay.inputs.is_empty());
+
+        let mut replayed = create_app();
+        replayed.play_replay(replay);
+        while replayed.round_ticks < app.round_ticks {
+            replayed.on_game_tick();
+        }
+        assert!(replayed.snake == app.snake);
+        assert_eq!(replayed.get_score(), app.get_score());
+        assert_eq!(replayed.round_time, app.round_time);
+        assert!(get_item_positions(&replayed) == get_item_positions(&app));
+    }
+
+    #[test]
+    fn rounds_with_the_same_seed_spawn_the_same_items() {
+        let rounds: Vec<Vec<(f64, f64)>> = (0..2)
+            .map(|_| {
+                let mut app = create_app();
+                let mut config = GameConfig::new(GameMode::Classic, (50, 25));
+                config.seed = 42;
+                app.start_round(config);
+                play_ticks(&mut app, 200);
+                get_item_positions(&app)
+            })
+            .collect();
+        assert!(!rounds[0].is_empty());
+        assert!(rounds[0] == rounds[1]);
+    }
+
+    #[test]
+    fn continued_round_goes_on_like_the_saved_one() {
+        let mut app = create_app();
+        let mut config = GameConfig::new(GameMode::Classic, (50, 25));
+        config.seed = 3;
+        app.start_round(config);
+        play_ticks(&mut app, 100);
+        // Long enough for a time bonus
+        for key in [KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right] {
+            app.handle_input_events(KeyEvent::from(key));
+            play_ticks(&mut app, 40);
+        }
+
+        let mut continued = create_app();
+        let json_data = serde_json::to_string(&app.get_save_game()).unwrap();
+        continued.saved_game = Some(serde_json::from_str(&json_data).unwrap());
+        continued.continue_round();
+        assert!(continued.is_resumed());
+        assert!(continued.saved_game.is_none());
+        assert!(continued.snake == app.snake);
+        assert_eq!(continued.get_score(), app.get_score());
+        assert_eq!(continued.round_ticks, app.round_ticks);
+        assert!(get_item_positions(&continued) == get_item_positions(&app));
+        assert!(!app.scoring.events.is_empty());
+        assert_eq!(continued.scoring.events.len(), app.scoring.events.len());
+        assert_eq!(
+            continued.scoring.get_speed_bonus(),
+            app.scoring.get_speed_bonus()
+        );
+        assert_eq!(continued.stats.turns.len(), 4);
+        assert_eq!(continued.stats.apples_eaten, app.stats.apples_eaten);
+        assert_eq!(continued.stats.collected, app.stats.collected);
+        let input_ticks = |game: &App| -> Vec<u64> {
+            game.recorded_inputs
+                .iter()
+                .map(|input| input.tick)
+                .collect()
+        };
+        assert_eq!(input_ticks(&continued), input_ticks(&app));
+
+        play_ticks(&mut continued, COUNTDOWN_TICKS as u64);
+        for key in [KeyCode::Up, KeyCode::Left, KeyCode::Down] {
+            for game in [&mut app, &mut continued] {
+                play_ticks(game, 30);
+                game.handle_input_events(KeyEvent::from(key));
+            }
+        }
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(continued.snake == app.snake);
+        assert_eq!(continued.get_score(), app.get_score());
+        assert!(get_item_positions(&continued) == get_item_positions(&app));
+    }
+
+    fn start_classic_round(app: &mut App) {
+        app.start_round(GameConfig::new(GameMode::Classic, (50, 25)));
+        play_ticks(app, COUNTDOWN_TICKS as u64);
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.handle_input_events(KeyEvent::from(key));
+    }
+
+    #[test]
+    fn going_back_returns_to_the_screen_below() {
+        let mut app = create_app();
+        let settings = app
+            .get_menu_entries()
+            .iter()
+            .position(|entry| *entry == MenuEntry::Settings);
+        app.menu_cursor = settings;
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(app.current_screen, CurrentScreen::Settings));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Esc);
+        assert!(matches!(app.current_screen, CurrentScreen::Menu));
+        assert_eq!(app.menu_cursor, settings);
+        assert!(!app.exit);
+    }
+
+    #[test]
+    fn quit_dialog_resumes_the_round_after_a_countdown() {
+        let mut app = create_app();
+        start_classic_round(&mut app);
+        press(&mut app, KeyCode::Esc);
+        assert!(matches!(app.current_screen, CurrentScreen::QuitConfirm));
+        let round_ticks = app.round_ticks;
+        play_ticks(&mut app, 10);
+        assert_eq!(app.round_ticks, round_ticks);
+
+        press(&mut app, KeyCode::Esc);
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert_eq!(app.countdown, COUNTDOWN_TICKS);
+
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(!app.exit);
+    }
+
+    #[test]
+    fn only_a_recorded_quit_ranks_the_round() {
+        for (entry, ranked) in [
+            (QuitEntry::RecordAndLeave, 1),
+            (QuitEntry::DiscardAndLeave, 0),
+        ] {
+            let mut app = create_app();
+            start_classic_round(&mut app);
+            press(&mut app, KeyCode::Esc);
+            // Rounds that are not kept on disk can not be saved
+            assert!(!app.get_quit_entries().contains(&QuitEntry::SaveAndLeave));
+            app.menu_cursor = app
+                .get_quit_entries()
+                .iter()
+                .position(|quit_entry| *quit_entry == entry);
+            press(&mut app, KeyCode::Enter);
+            assert!(matches!(app.current_screen, CurrentScreen::Menu));
+            assert!(!app.exit);
+            assert_eq!(
+                app.score_manager.get_scores(GameMode::Classic).len(),
+                ranked
+            );
+        }
+    }
+
+    #[test]
+    fn leaving_the_daily_challenge_uses_up_the_attempt() {
+        let mut app = create_app();
+        app.start_daily_challenge();
+        assert!(!app.practice_round);
+        press(&mut app, KeyCode::Esc);
+        app.menu_cursor = app
+            .get_quit_entries()
+            .iter()
+            .position(|entry| *entry == QuitEntry::DiscardAndLeave);
+        press(&mut app, KeyCode::Enter);
+
+        app.start_daily_challenge();
+        assert!(app.practice_round);
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+    }
+
+    #[test]
+    fn saved_daily_challenge_has_to_be_continued() {
+        let mut app = create_app();
+        app.start_daily_challenge();
+        app.saved_game = Some(app.get_save_game());
+        app.open_menu(MenuEntry::Continue);
+        app.start_daily_challenge();
+        assert!(matches!(app.current_screen, CurrentScreen::Menu));
+        assert_eq!(app.toasts.len(), 1);
+
+        app.continue_round();
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(!app.practice_round);
+    }
+
+    #[test]
+    fn golden_apples_count_toward_the_apple_goals() {
+        let mut app = create_app();
+        app.start_round(GameConfig::new(GameMode::TimeAttack, (50, 25)));
+        app.emit(GameEvent::Collected(APPLE));
+        app.emit(GameEvent::Collected(GOLDEN_APPLE));
+        assert_eq!(app.get_score(), 2);
+
+        app.start_round(GameConfig::new(GameMode::AppleRush, (50, 25)));
+        for _ in 0..GameMode::AppleRush.get_apple_goal().unwrap() {
+            app.emit(GameEvent::Collected(GOLDEN_APPLE));
+        }
+        app.game_update();
+        assert!(matches!(app.current_screen, CurrentScreen::Lost));
+        assert_eq!(app.notice.as_deref(), Some("All apples eaten!"));
+    }
+
+    #[test]
+    fn shield_lets_the_snake_pass_through_after_a_tail_hit() {
+        let mut app = create_app();
+        start_classic_round(&mut app);
+        app.collectables.clear();
+        app.hazards.clear();
+        // The head moves up into a loop of the body that is two cells long
+        app.snake = vec![
+            (10.0, 10.0),
+            (9.0, 10.0),
+            (9.0, 10.5),
+            (9.0, 11.0),
+            (9.0, 11.5),
+            (10.0, 11.5),
+            (10.0, 11.0),
+            (10.0, 10.5),
+            (11.0, 10.5),
+            (12.0, 10.5),
+            (13.0, 10.5),
+        ];
+        app.direction = Direction::Up;
+        app.add_effect(SHIELD, 30);
+
+        app.game_update();
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert!(!app.has_effect(SHIELD));
+        assert!(app.has_effect(GHOST));
+        app.game_update();
+        assert!(matches!(app.current_screen, CurrentScreen::Main));
+        assert_eq!(app.snake[0], (10.0, 11.0));
+    }
+
+    #[test]
+    fn saves_without_a_field_or_a_snake_are_not_continued() {
+        let mut app = create_app();
+        start_classic_round(&mut app);
+        assert!(app.get_save_game().is_valid());
+
+        let mut save = app.get_save_game();
+        save.config.field_size = (0, 0);
+        assert!(!save.is_valid());
+        let mut save = app.get_save_game();
+        save.snake.clear();
+        assert!(!save.is_valid());
+    }
 
-        false
+    #[test]
+    fn apple_rush_is_timed_to_the_tick() {
+        let mut app = create_app();
+        app.start_round(GameConfig::new(GameMode::AppleRush, (50, 25)));
+        app.round_ticks = 1231;
+        assert_eq!(app.get_score(), 1231);
+        assert_eq!(
+            GameMode::AppleRush.format_score(app.get_score()),
+            "01:01.55"
+        );
+        app.round_ticks = 1232;
+        assert!(app.get_score() > 1231);
     }
 }
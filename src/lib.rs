@@ -0,0 +1,15 @@
+//! Snake for the terminal, built on ratatui
+//!
+//! The game can be run on its own with [`App::run`], rendered into any frame
+//! with [`render`], or driven headless through [`App::handle_input_events`]
+//! and [`App::on_game_tick`].
+
+pub mod game;
+pub mod ui;
+pub mod utils;
+
+pub use game::App;
+pub use ui::{SnakeGameWidget, render};
+pub use utils::collectables::Collectable;
+pub use utils::registry::{CollectableDefinition, CollectableRegistry};
+pub use utils::scores::ScoreManager;
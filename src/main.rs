@@ -1,12 +1,36 @@
-use std::io;
+use std::{env, io, process::ExitCode};
 
-use game::App;
+use snake_ratatui::{App, utils::replay::Replay};
 
-mod game;
-mod ui;
-mod utils;
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("snake-ratatui: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-fn main() -> io::Result<()> {
-    let app_result = App::new().run();
-    return app_result;
+fn run() -> io::Result<()> {
+    let mut app = App::new();
+    // `snake-ratatui --replay <file>` plays a saved replay
+    if let Some(path) = env::args().skip_while(|arg| arg != "--replay").nth(1) {
+        let replay = Replay::load(&path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("could not load the replay {}: {}", path, err),
+            )
+        })?;
+        app.play_replay(replay);
+    }
+    #[cfg(feature = "tokio")]
+    {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()?
+            .block_on(app.run_async())
+    }
+    #[cfg(not(feature = "tokio"))]
+    app.run()
 }
@@ -0,0 +1,57 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Paragraph, Widget, Wrap},
+};
+
+use crate::{game::App, utils::achievements::ACHIEVEMENTS};
+
+/// Every achievement with a mark if it is unlocked
+pub struct AchievementsScreen<'a> {
+    app: &'a App,
+}
+
+impl<'a> AchievementsScreen<'a> {
+    pub fn new(app: &'a App) -> Self {
+        AchievementsScreen { app }
+    }
+}
+
+impl Widget for AchievementsScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = &self.app.theme;
+        let store = self.app.get_achievements();
+        let achievements_block = Block::default()
+            .title(format!(
+                "Achievements {}/{}",
+                store.get_unlocked_count(),
+                ACHIEVEMENTS.len()
+            ))
+            .title_bottom(Line::from("Esc back").centered())
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::QuadrantInside)
+            .style(Style::default().fg(theme.text));
+
+        let lines: Vec<Line> = ACHIEVEMENTS
+            .iter()
+            .map(|achievement| {
+                let (mark, name) = if store.is_unlocked(achievement.id) {
+                    ("✔ ", Span::from(achievement.name).fg(theme.accent).bold())
+                } else {
+                    ("· ", Span::from(achievement.name))
+                };
+                Line::from(vec![
+                    Span::from(mark),
+                    name,
+                    Span::from(format!(" - {}", achievement.description)).dim(),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(achievements_block)
+            .render(area, buf);
+    }
+}
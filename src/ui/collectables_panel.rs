@@ -0,0 +1,79 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, LineGauge, Paragraph, Widget},
+};
+
+use crate::{game::App, utils::enums::CurrentScreen};
+
+/// Every active effect with a bar counting down its remaining time,
+/// followed by the items that are currently on the field
+pub struct CollectablesPanel<'a> {
+    app: &'a App,
+}
+
+impl<'a> CollectablesPanel<'a> {
+    pub fn new(app: &'a App) -> Self {
+        CollectablesPanel { app }
+    }
+}
+
+impl Widget for CollectablesPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let title = match app.current_screen {
+            CurrentScreen::Main => {
+                format!("Collectables - next in {} seconds", app.random_item_timer)
+            }
+            _ => String::from("Collectables"),
+        };
+        let block = Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .title(title);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [effects_area, items_area] = Layout::vertical([
+            Constraint::Length(app.effects.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner_area);
+        let effect_areas =
+            Layout::vertical(app.effects.iter().map(|_| Constraint::Length(1))).split(effects_area);
+        for (effect, effect_area) in app.effects.iter().zip(effect_areas.iter()) {
+            let Some(definition) = app.registry.get(effect.effect_type) else {
+                continue;
+            };
+            LineGauge::default()
+                .label(format!("{} {:>2}s", definition.name, effect.remaining_time))
+                .filled_style(Style::default().fg(app.theme.get_effect_color(definition)))
+                .line_set(symbols::line::THICK)
+                .ratio(effect.get_progress())
+                .render(*effect_area, buf);
+        }
+
+        let item_lines: Vec<Line> = app
+            .collectables
+            .iter()
+            .filter_map(|spawned| {
+                let definition = app.registry.get(spawned.collectable_type)?;
+                let mut spans = vec![
+                    Span::from(format!("{} ", definition.glyph)).style(
+                        Style::default().fg(app
+                            .theme
+                            .get_collectable_color(definition.id, definition.color)),
+                    ),
+                    Span::from(definition.name),
+                ];
+                if let Some(despawn_timer) = spawned.despawn_timer {
+                    spans.push(Span::from(format!(" ({}s)", despawn_timer)));
+                }
+                Some(Line::from(spans))
+            })
+            .collect();
+        Paragraph::new(item_lines).render(items_area, buf);
+    }
+}
@@ -0,0 +1,302 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Widget,
+        canvas::{Canvas, Context, Painter, Shape},
+    },
+};
+
+use crate::{
+    game::App,
+    utils::{
+        animations::{AnimationKind, TICKS_PER_SECOND},
+        arena::Arena,
+        enums::{Direction, RenderStyle, SnakeSkin},
+        helpers::get_direction_from_vector,
+    },
+};
+
+/// The playing field with the snake, every collectable and hazard on it and the running animations
+///
+/// Glyphs are drawn on the text layer of the canvas. A terminal cell holds two rows of
+/// the field, so a glyph hides whatever else is drawn in its cell.
+pub struct GameCanvas<'a> {
+    app: &'a App,
+}
+
+impl<'a> GameCanvas<'a> {
+    pub fn new(app: &'a App) -> Self {
+        GameCanvas { app }
+    }
+}
+
+impl Widget for GameCanvas<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let block = Block::default()
+            .title("Game Area")
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::QuadrantInside);
+        let inner_area = block.inner(area);
+        let render_style = app.settings.render_style;
+        let marker = match render_style {
+            RenderStyle::HalfBlock | RenderStyle::Glyphs => Marker::HalfBlock,
+            RenderStyle::Braille => Marker::Braille,
+            RenderStyle::FullBlock => Marker::Block,
+        };
+        let labels = LabelGrid {
+            field_size: app.field_size,
+            area_size: (inner_area.width, inner_area.height),
+        };
+        Canvas::default()
+            .block(block)
+            .x_bounds([0.0, app.field_size.0 as f64])
+            .y_bounds([0.0, app.field_size.1 as f64])
+            .marker(marker)
+            .paint(|ctx| {
+                if let Some(arena) = &app.arena {
+                    draw_arena(ctx, app, arena);
+                }
+                for spawned in &app.collectables {
+                    let definition = app.registry.get(spawned.collectable_type);
+                    let color = app.theme.get_collectable_color(
+                        spawned.collectable_type,
+                        definition.map_or(app.theme.text, |definition| definition.color),
+                    );
+                    match (render_style, definition) {
+                        (RenderStyle::Glyphs, Some(definition)) => {
+                            for position in spawned.collectable.get_positions() {
+                                labels.print(ctx, position, &definition.glyph.to_string(), color);
+                            }
+                        }
+                        _ => ctx.draw(&FieldCells {
+                            coords: &spawned.collectable.get_positions(),
+                            color,
+                            field_size: app.field_size,
+                        }),
+                    }
+                }
+                let hazards: Vec<(f64, f64)> =
+                    app.hazards.iter().map(|hazard| hazard.position).collect();
+                match render_style {
+                    RenderStyle::Glyphs => {
+                        for position in &hazards {
+                            labels.print(ctx, *position, "■", app.theme.danger);
+                        }
+                    }
+                    _ => ctx.draw(&FieldCells {
+                        coords: &hazards,
+                        color: app.theme.danger,
+                        field_size: app.field_size,
+                    }),
+                }
+                draw_snake(ctx, app, &labels);
+                draw_animations(ctx, app, &labels);
+            })
+            .render(area, buf);
+
+        if app.countdown > 0 {
+            let seconds = app.countdown.div_ceil(TICKS_PER_SECOND);
+            // Above the middle, where the snake starts
+            let [_, row, _] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(2),
+            ])
+            .areas(inner_area);
+            Line::from(seconds.to_string())
+                .fg(app.theme.accent)
+                .bold()
+                .centered()
+                .render(row, buf);
+        }
+    }
+}
+
+/// Draws the walls that moved in and, shortly before the next step, the cells they will take
+fn draw_arena(ctx: &mut Context, app: &App, arena: &Arena) {
+    if arena.inset > 0 {
+        ctx.draw(&FieldCells {
+            coords: &Arena::get_border(arena.inset - 1, app.field_size),
+            color: app.theme.text,
+            field_size: app.field_size,
+        });
+    }
+    if arena.is_shrinking_soon() && app.tick {
+        ctx.draw(&FieldCells {
+            coords: &Arena::get_border(arena.inset, app.field_size),
+            color: app.theme.danger,
+            field_size: app.field_size,
+        });
+    }
+}
+
+fn draw_animations(ctx: &mut Context, app: &App, labels: &LabelGrid) {
+    for animation in &app.animations {
+        let (x, y) = animation.position;
+        match animation.kind {
+            AnimationKind::Flash(collectable_type) => {
+                let color = app.theme.get_collectable_color(
+                    collectable_type,
+                    app.registry
+                        .get(collectable_type)
+                        .map_or(app.theme.text, |definition| definition.color),
+                );
+                // A ring that grows by one cell every few ticks
+                let radius = 1 + (animation.get_progress() * 2.0) as i32;
+                let ring: Vec<(f64, f64)> = (-radius..=radius)
+                    .flat_map(|dx| {
+                        let dy = (radius - dx.abs()) as f64 * 0.5;
+                        [(x + dx as f64, y + dy), (x + dx as f64, y - dy)]
+                    })
+                    .collect();
+                ctx.draw(&FieldCells {
+                    coords: &ring,
+                    color,
+                    field_size: app.field_size,
+                });
+            }
+            AnimationKind::ScorePopup(score_change) => {
+                let text = format!("{:+}", score_change);
+                let color = if score_change > 0 {
+                    app.theme.accent
+                } else {
+                    app.theme.danger
+                };
+                let rise = (animation.get_progress() * 4.0).floor() * 0.5;
+                labels.print(ctx, (x, y + rise), &text, color);
+            }
+            AnimationKind::Death => {}
+        }
+    }
+}
+
+fn draw_snake(ctx: &mut Context, app: &App, labels: &LabelGrid) {
+    let theme = &app.theme;
+    let death_progress = app.animations.iter().find_map(|animation| {
+        matches!(animation.kind, AnimationKind::Death).then(|| animation.get_progress())
+    });
+    if let Some(progress) = death_progress {
+        // The snake blinks while it dissolves from the tail
+        let visible = (app.snake.len() as f64 * (1.0 - progress)).ceil() as usize;
+        ctx.draw(&FieldCells {
+            coords: &app.snake[..visible.min(app.snake.len())],
+            color: if app.tick {
+                theme.danger
+            } else {
+                theme.snake_body
+            },
+            field_size: app.field_size,
+        });
+        return;
+    }
+    match app.settings.snake_skin {
+        SnakeSkin::Gradient => {
+            let body_length = app.snake.len().saturating_sub(2).max(1) as f64;
+            for (index, segment) in app.snake.iter().enumerate().skip(1) {
+                ctx.draw(&FieldCells {
+                    coords: &[*segment],
+                    color: theme.get_gradient_color((index - 1) as f64 / body_length),
+                    field_size: app.field_size,
+                });
+            }
+        }
+        _ => ctx.draw(&FieldCells {
+            coords: &app.snake[1..],
+            color: theme.snake_body,
+            field_size: app.field_size,
+        }),
+    }
+    ctx.draw(&FieldCells {
+        coords: &app.snake[..1],
+        color: theme.snake_head,
+        field_size: app.field_size,
+    });
+
+    if app.settings.snake_skin == SnakeSkin::Directional && app.snake.len() > 1 {
+        let head_glyph = match app.direction {
+            Direction::Up => '▲',
+            Direction::Down => '▼',
+            Direction::Left => '◀',
+            Direction::Right => '▶',
+        };
+        let tail = app.snake[app.snake.len() - 1];
+        let before_tail = app.snake[app.snake.len() - 2];
+        // A step of more than one cell means the snake wrapped around the edge
+        let unwrap = |delta: f64, step: f64| {
+            if delta.abs() > step { -delta } else { delta }
+        };
+        let tail_vector = (
+            unwrap(tail.0 - before_tail.0, 1.0),
+            unwrap(tail.1 - before_tail.1, 0.5),
+        );
+        let tail_glyph = match get_direction_from_vector(&tail_vector) {
+            Direction::Up => '╹',
+            Direction::Down => '╻',
+            Direction::Left => '╸',
+            Direction::Right => '╺',
+        };
+        labels.print(ctx, tail, &tail_glyph.to_string(), theme.snake_body);
+        labels.print(ctx, app.snake[0], &head_glyph.to_string(), theme.snake_head);
+    }
+}
+
+/// Places labels of the canvas text layer on the terminal cell of a field cell
+struct LabelGrid {
+    field_size: (u32, u32),
+    area_size: (u16, u16),
+}
+
+impl LabelGrid {
+    fn print(&self, ctx: &mut Context, position: (f64, f64), text: &str, color: Color) {
+        let (width, height) = (self.field_size.0 as f64, self.field_size.1 as f64);
+        let (x, y) = position;
+        if x <= 0.0 || x > width || y < 0.0 || y >= height || self.area_size.0 < 2 {
+            return;
+        }
+        let (columns, rows) = (self.area_size.0 as f64, self.area_size.1 as f64);
+        // The canvas maps labels onto one cell less than the area in each direction
+        let column = ((x - 1.0) * columns / width).floor();
+        let row = ((height - y - 0.5) * rows / height).floor();
+        let label_x = ((column + 0.5) * width / (columns - 1.0)).min(width);
+        let label_y = (height - (row + 0.5) * height / (rows - 1.0).max(1.0)).max(0.0);
+        ctx.print(label_x, label_y, Span::from(text.to_string()).fg(color));
+    }
+}
+
+/// Fills whole cells of the field, so the field keeps its look when it is scaled up
+///
+/// A cell at `(x, y)` spans from `x - 1` to `x` and from `y` to `y + 0.5`
+struct FieldCells<'a> {
+    coords: &'a [(f64, f64)],
+    color: Color,
+    field_size: (u32, u32),
+}
+
+impl Shape for FieldCells<'_> {
+    fn draw(&self, painter: &mut Painter) {
+        let (width, height) = (self.field_size.0 as f64, self.field_size.1 as f64);
+        let Some((max_x, max_y)) = painter.get_point(width, 0.0) else {
+            return;
+        };
+        let (resolution_x, resolution_y) = ((max_x + 1) as f64, (max_y + 1) as f64);
+        for &(x, y) in self.coords {
+            if x <= 0.0 || x > width || y < 0.0 || y >= height {
+                continue;
+            }
+            let left = ((x - 1.0) * resolution_x / width) as usize;
+            let right = ((x * resolution_x / width) as usize).max(left + 1);
+            let top = ((height - y - 0.5) * resolution_y / height) as usize;
+            let bottom = (((height - y) * resolution_y / height) as usize).max(top + 1);
+            for pixel_x in left..right.min(max_x + 1) {
+                for pixel_y in top..bottom.min(max_y + 1) {
+                    painter.paint(pixel_x, pixel_y, self.color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::{
+    game::App,
+    utils::{enums::GameMode, helpers::convert_seconds_to_string},
+};
+
+/// Game mode, score, round time, speed and running combo of the current round
+pub struct GameInfo<'a> {
+    app: &'a App,
+}
+
+impl<'a> GameInfo<'a> {
+    pub fn new(app: &'a App) -> Self {
+        GameInfo { app }
+    }
+}
+
+impl Widget for GameInfo<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let game_mode = app.config.game_mode;
+        let mut score_lines = vec![Line::from(game_mode.get_label()).fg(app.theme.accent)];
+        let score_span = match game_mode.get_apple_goal() {
+            Some(goal) => Span::from(format!("Apples: {}/{}", app.stats.apples_eaten, goal)),
+            None if game_mode == GameMode::TimeAttack => {
+                Span::from(format!("Apples: {}", app.stats.apples_eaten))
+            }
+            None => Span::from(format!("Score: {}", app.get_score())),
+        };
+        // Time Attack counts down to the end of the round
+        let time_span = match game_mode.get_time_limit() {
+            Some(limit) => Span::from(format!(
+                "Time left: {}",
+                convert_seconds_to_string(&limit.saturating_sub(app.round_time))
+            )),
+            None => Span::from(format!(
+                "Time: {}",
+                convert_seconds_to_string(&app.round_time)
+            )),
+        };
+        score_lines.push(Line::from(score_span));
+        score_lines.push(Line::from(time_span));
+        let speed_color = match app.game_speed {
+            speed if speed <= 0 => app.theme.speed_normal,
+            speed if speed <= 1 => app.theme.speed_fast,
+            _ => app.theme.speed_max,
+        };
+        let speed_text = Span::from(format!("Speed: {:.2}", app.game_speed))
+            .style(Style::default().fg(speed_color));
+        score_lines.push(Line::from(speed_text));
+        let tick = app.get_round_ticks();
+        let combo = app.scoring.get_combo(tick);
+        if game_mode.is_scored_by_points() && combo > 0 {
+            score_lines.push(
+                Line::from(format!(
+                    "Combo: {} · x{}",
+                    combo,
+                    app.scoring.get_multiplier()
+                ))
+                .fg(app.theme.accent),
+            );
+            // Runs out until the next apple has to be eaten
+            let bar_width = area.width.saturating_sub(2) as usize;
+            let filled = (app.scoring.get_combo_time_left(tick) * bar_width as f64).ceil() as usize;
+            score_lines.push(
+                Line::from(format!(
+                    "{}{}",
+                    "█".repeat(filled),
+                    "░".repeat(bar_width.saturating_sub(filled))
+                ))
+                .fg(app.theme.accent),
+            );
+        }
+        if let Some(seconds) = app
+            .arena
+            .as_ref()
+            .and_then(|arena| arena.get_seconds_until_shrink())
+        {
+            score_lines.push(Line::from(format!("Walls move in: {}s", seconds)));
+        }
+        if let Some(date) = &app.config.challenge_date {
+            let modifiers = &app.config.modifiers;
+            score_lines.push(Line::from(format!("Date: {}", date)));
+            if modifiers.open_walls {
+                score_lines.push(Line::from("Open walls"));
+            }
+            score_lines.push(Line::from(modifiers.item_mix.get_label()));
+            if !app.is_ranked() {
+                score_lines.push(Line::from("Practice").fg(app.theme.danger));
+            }
+        }
+
+        Paragraph::new(score_lines)
+            .block(
+                Block::default()
+                    .borders(ratatui::widgets::Borders::ALL)
+                    .title("Game Info"),
+            )
+            .render(area, buf);
+    }
+}
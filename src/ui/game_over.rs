@@ -0,0 +1,124 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    game::App,
+    ui::menu::get_cursor_text,
+    utils::{enums::LostEntry, helpers::convert_seconds_to_string},
+};
+
+/// Summary of the round shown in the middle of the given area after the snake died,
+/// together with the actions to continue
+pub struct GameOverPopup<'a> {
+    app: &'a App,
+}
+
+impl<'a> GameOverPopup<'a> {
+    pub fn new(app: &'a App) -> Self {
+        GameOverPopup { app }
+    }
+}
+
+impl Widget for GameOverPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let theme = &app.theme;
+        let stats = &app.stats;
+
+        let mut lines = vec![];
+        let mut score_spans = vec![
+            Span::from(format!(
+                "Score: {}",
+                app.config.game_mode.format_score(app.get_score())
+            ))
+            .bold(),
+        ];
+        if let Some(rank) = stats.rank {
+            score_spans.push(Span::from(format!(" · Rank #{}", rank)));
+        }
+        lines.push(Line::from(score_spans));
+        if stats.personal_best && stats.rank.is_some() {
+            lines.push(Line::from("New personal best!").fg(theme.accent));
+        }
+        if app.config.game_mode.is_scored_by_points() {
+            let scoring = &app.scoring;
+            let bonuses: Vec<String> = [
+                ("Combo", scoring.get_combo_bonus()),
+                ("Speed", scoring.get_speed_bonus()),
+                ("Time", scoring.get_time_bonus()),
+            ]
+            .iter()
+            .filter(|(_, points)| *points > 0)
+            .map(|(name, points)| format!("{} +{}", name, points))
+            .collect();
+            if !bonuses.is_empty() {
+                lines.push(Line::from(bonuses.join(" · ")));
+            }
+        }
+        if let Some(death_cause) = stats.death_cause {
+            lines.push(Line::from(death_cause.get_label()).fg(theme.danger));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Time: {}",
+            convert_seconds_to_string(&app.round_time)
+        )));
+        lines.push(Line::from(format!("Apples eaten: {}", stats.apples_eaten)));
+        lines.push(Line::from(format!("Max speed: {}", stats.max_speed)));
+        for (collectable_type, count) in &stats.collected {
+            if let Some(definition) = app.registry.get(collectable_type) {
+                let color = theme.get_collectable_color(definition.id, definition.color);
+                lines.push(Line::from(vec![
+                    Span::from(format!("{} ", definition.glyph)).fg(color),
+                    Span::from(format!("{} x{}", definition.name, count)),
+                ]));
+            }
+        }
+        if let Some(notice) = &app.notice {
+            lines.push(Line::from(""));
+            lines.push(Line::from(notice.clone()).fg(theme.accent));
+        }
+
+        let height = lines.len() as u16 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let title = if app.is_replaying() {
+            "Replay Over"
+        } else {
+            "Game Over"
+        };
+        let lost_block = Block::default()
+            .title(title)
+            .borders(ratatui::widgets::Borders::ALL)
+            .style(Style::default().fg(theme.danger));
+        let inner_area = lost_block.inner(area);
+        Clear.render(area, buf);
+        lost_block.render(area, buf);
+
+        let [stats_area, actions_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner_area);
+        Paragraph::new(lines)
+            .style(Style::default().fg(theme.text))
+            .centered()
+            .render(stats_area, buf);
+
+        let action_areas =
+            Layout::horizontal(LostEntry::ALL.map(|_| Constraint::Fill(1))).split(actions_area);
+        for (i, (entry, entry_area)) in LostEntry::ALL.iter().zip(action_areas.iter()).enumerate() {
+            Paragraph::new(get_cursor_text(app, i, entry.get_label()))
+                .style(Style::default().fg(theme.text))
+                .centered()
+                .render(*entry_area, buf);
+        }
+    }
+}
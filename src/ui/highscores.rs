@@ -0,0 +1,72 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Paragraph, Scrollbar, ScrollbarState, StatefulWidget, Widget},
+};
+
+use crate::utils::scores::Score;
+
+/// The leaderboard with a scrollbar, the state is the scroll position
+pub struct Highscores<'a> {
+    scores: &'a [Score],
+    title: String,
+}
+
+impl<'a> Highscores<'a> {
+    pub fn new(scores: &'a [Score]) -> Self {
+        Highscores {
+            scores,
+            title: String::from("Highscores"),
+        }
+    }
+
+    /// Changes the heading above the scores
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+}
+
+impl StatefulWidget for Highscores<'_> {
+    type State = usize;
+
+    fn render(self, area: Rect, buf: &mut Buffer, vertical_scroll: &mut usize) {
+        let block = Block::default()
+            .borders(ratatui::widgets::Borders::ALL)
+            .style(Style::default());
+        let mut scores: Vec<Line> = self
+            .scores
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                Line::from(format!(
+                    "# {}: {} - {}{}",
+                    i + 1,
+                    s.player_name,
+                    s.game_mode.format_score(s.score),
+                    // Marks rounds that were saved and continued
+                    if s.resumed { " ↺" } else { "" }
+                ))
+            })
+            .collect();
+        scores.insert(0, Line::from(format!("{}:", self.title)));
+        *vertical_scroll = (*vertical_scroll).min(scores.len().saturating_sub(1));
+
+        let mut scrollbar_state = ScrollbarState::new(scores.len()).position(*vertical_scroll);
+        Paragraph::new(scores)
+            .scroll((*vertical_scroll as u16, 0))
+            .block(block)
+            .render(area, buf);
+        Scrollbar::default().render(
+            area.inner(Margin {
+                // using an inner vertical margin of 1 unit makes the scrollbar inside the block
+                vertical: 1,
+                horizontal: 0,
+            }),
+            buf,
+            &mut scrollbar_state,
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::Text,
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+
+use crate::{game::App, utils::enums::MenuEntry};
+
+/// The main menu with blinking arrows around the selected entry
+pub struct Menu<'a> {
+    app: &'a App,
+}
+
+impl<'a> Menu<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Menu { app }
+    }
+}
+
+impl Widget for Menu<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let entries = self.app.get_menu_entries();
+        let menu_block = Block::default()
+            .title("Menu")
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::QuadrantInside);
+        let inner_area = menu_block.inner(area);
+        menu_block.render(area, buf);
+
+        // Entries get their own box if there is enough room for it
+        let boxed = inner_area.height as usize >= entries.len() * 3;
+        let [button_area] = Layout::horizontal([Constraint::Length(24)])
+            .flex(Flex::Center)
+            .areas(inner_area);
+        let menu_layout = Layout::vertical(
+            entries
+                .iter()
+                .map(|_| Constraint::Length(if boxed { 3 } else { 1 })),
+        )
+        .flex(Flex::Center)
+        .split(button_area);
+
+        for (i, (entry, entry_area)) in entries.iter().zip(menu_layout.iter()).enumerate() {
+            let text = get_cursor_text(self.app, i, entry.get_label());
+            let color = match entry {
+                MenuEntry::StartGame => self.app.theme.accent,
+                MenuEntry::Quit => self.app.theme.danger,
+                _ => self.app.theme.text,
+            };
+            let mut paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .centered();
+            if boxed {
+                paragraph =
+                    paragraph.block(Block::default().borders(ratatui::widgets::Borders::ALL));
+            }
+            paragraph.render(*entry_area, buf);
+        }
+    }
+}
+
+/// Returns the label with blinking arrows around it if the cursor is on it
+pub fn get_cursor_text(app: &App, index: usize, label: &str) -> Text<'static> {
+    match app.menu_cursor {
+        Some(cursor) if cursor == index && !app.tick => Text::from(format!("-> {} <-", label)),
+        _ => Text::from(label.to_string()),
+    }
+}
@@ -0,0 +1,39 @@
+//! Widgets that make up the game screen
+//!
+//! [`SnakeGameWidget`] renders the whole game, the other widgets can be used
+//! on their own to place single panels into a custom layout.
+
+use ratatui::Frame;
+
+use crate::game::App;
+
+pub mod achievements_screen;
+pub mod collectables_panel;
+pub mod game_canvas;
+pub mod game_info;
+pub mod game_over;
+pub mod highscores;
+pub mod menu;
+pub mod quit_dialog;
+pub mod settings_menu;
+pub mod snake_game;
+pub mod stats_screen;
+pub mod toasts;
+
+pub use achievements_screen::AchievementsScreen;
+pub use collectables_panel::CollectablesPanel;
+pub use game_canvas::GameCanvas;
+pub use game_info::GameInfo;
+pub use game_over::GameOverPopup;
+pub use highscores::Highscores;
+pub use menu::Menu;
+pub use quit_dialog::QuitDialog;
+pub use settings_menu::SettingsMenu;
+pub use snake_game::SnakeGameWidget;
+pub use stats_screen::StatsScreen;
+pub use toasts::Toasts;
+
+/// Renders the whole game into the frame
+pub fn render(frame: &mut Frame, app: &mut App) {
+    frame.render_stateful_widget(SnakeGameWidget, frame.area(), app);
+}
@@ -0,0 +1,63 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::{game::App, ui::menu::get_cursor_text};
+
+/// Dialog in the middle of the given area that asks what happens to the running round
+/// before the game is closed
+pub struct QuitDialog<'a> {
+    app: &'a App,
+}
+
+impl<'a> QuitDialog<'a> {
+    pub fn new(app: &'a App) -> Self {
+        QuitDialog { app }
+    }
+}
+
+impl Widget for QuitDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let theme = &app.theme;
+        let entries = app.get_quit_entries();
+
+        let height = entries.len() as u16 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(40)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::default()
+            .title("Leave the round?")
+            .borders(ratatui::widgets::Borders::ALL)
+            .style(Style::default().fg(theme.accent));
+        let inner_area = block.inner(area);
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let mut lines = vec![
+            Line::from(format!(
+                "Score: {}",
+                app.config.game_mode.format_score(app.get_score())
+            ))
+            .bold(),
+            Line::from(""),
+        ];
+        lines.extend(
+            entries.iter().enumerate().map(|(i, entry)| {
+                Line::from(get_cursor_text(app, i, entry.get_label()).to_string())
+            }),
+        );
+        Paragraph::new(lines)
+            .style(Style::default().fg(theme.text))
+            .centered()
+            .render(inner_area, buf);
+    }
+}
@@ -0,0 +1,53 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+
+use crate::{game::App, ui::menu::get_cursor_text, utils::enums::SettingsEntry};
+
+/// List of all settings with their current values
+pub struct SettingsMenu<'a> {
+    app: &'a App,
+}
+
+impl<'a> SettingsMenu<'a> {
+    pub fn new(app: &'a App) -> Self {
+        SettingsMenu { app }
+    }
+}
+
+impl Widget for SettingsMenu<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let settings_block = Block::default()
+            .title("Settings")
+            .title_bottom(Line::from("←/→ change · Esc back").centered())
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::QuadrantInside);
+        let inner_area = settings_block.inner(area);
+        settings_block.render(area, buf);
+
+        let entry_areas =
+            Layout::vertical(SettingsEntry::ALL.iter().map(|_| Constraint::Length(1)))
+                .flex(Flex::Center)
+                .spacing(1)
+                .split(inner_area);
+        for (i, (entry, entry_area)) in SettingsEntry::ALL
+            .iter()
+            .zip(entry_areas.iter())
+            .enumerate()
+        {
+            let label = format!(
+                "{}: {}",
+                entry.get_label(),
+                self.app.get_setting_value(*entry)
+            );
+            Paragraph::new(get_cursor_text(self.app, i, &label))
+                .style(Style::default().fg(self.app.theme.text))
+                .centered()
+                .render(*entry_area, buf);
+        }
+    }
+}
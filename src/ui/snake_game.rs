@@ -0,0 +1,217 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    game::App,
+    ui::{
+        achievements_screen::AchievementsScreen, collectables_panel::CollectablesPanel,
+        game_canvas::GameCanvas, game_info::GameInfo, game_over::GameOverPopup,
+        highscores::Highscores, menu::Menu, quit_dialog::QuitDialog, settings_menu::SettingsMenu,
+        stats_screen::StatsScreen, toasts::Toasts,
+    },
+    utils::{
+        config::{FIELD_HEIGHTS, FIELD_WIDTHS},
+        enums::CurrentScreen,
+    },
+};
+
+const SIDE_PANEL_WIDTH: u16 = 30;
+const MIN_SIDE_PANEL_WIDTH: u16 = 20;
+const STACKED_PANEL_HEIGHT: u16 = 10;
+
+/// The complete game with its side panels, rendered into any area of a frame
+///
+/// The panels are placed next to the field if there is enough room, stacked below it
+/// on narrow terminals and hidden if only the field fits. On large terminals the field
+/// is scaled up by whole multiples of its size.
+#[derive(Default)]
+pub struct SnakeGameWidget;
+
+/// Areas of the single parts of the game, panels that do not fit are `None`
+struct GameLayout {
+    canvas: Rect,
+    side: Option<Rect>,
+    info: Option<Rect>,
+    collectables: Option<Rect>,
+    highscores: Option<Rect>,
+}
+
+impl SnakeGameWidget {
+    /// Returns the largest field size that still fits next to the side panels
+    pub fn get_field_size_for((width, height): (u16, u16)) -> (u32, u32) {
+        let field_width = if width >= 2 * SIDE_PANEL_WIDTH + 42 {
+            width - 2 * SIDE_PANEL_WIDTH - 2
+        } else {
+            width.saturating_sub(2)
+        };
+        let field_height = height.saturating_sub(2);
+        (
+            (field_width as u32).clamp(*FIELD_WIDTHS.start(), *FIELD_WIDTHS.end()),
+            (field_height as u32).clamp(*FIELD_HEIGHTS.start(), *FIELD_HEIGHTS.end()),
+        )
+    }
+
+    fn get_layout(area: Rect, field_size: (u32, u32)) -> Option<GameLayout> {
+        let field_width = field_size.0 as u16;
+        let field_height = field_size.1 as u16;
+        if area.width < field_width + 2 || area.height < field_height + 2 {
+            return None;
+        }
+        let get_canvas_size = |width: u16, height: u16| {
+            let scale = ((width - 2) / field_width)
+                .min((height - 2) / field_height)
+                .max(1);
+            (field_width * scale + 2, field_height * scale + 2)
+        };
+
+        if area.width >= field_width + 2 + 2 * MIN_SIDE_PANEL_WIDTH {
+            let side_width = ((area.width - field_width - 2) / 2).min(SIDE_PANEL_WIDTH);
+            let (canvas_width, canvas_height) =
+                get_canvas_size(area.width - 2 * side_width, area.height);
+            let [row] = Layout::vertical([Constraint::Length(canvas_height)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [side, canvas, highscores] = Layout::horizontal([
+                Constraint::Length(side_width),
+                Constraint::Length(canvas_width),
+                Constraint::Length(side_width),
+            ])
+            .flex(Flex::Center)
+            .areas(row);
+            let [info, collectables] =
+                Layout::vertical([Constraint::Percentage(30), Constraint::Percentage(70)])
+                    .areas(side);
+            Some(GameLayout {
+                canvas,
+                side: Some(side),
+                info: Some(info),
+                collectables: Some(collectables),
+                highscores: Some(highscores),
+            })
+        } else if area.height >= field_height + 2 + STACKED_PANEL_HEIGHT {
+            let (canvas_width, canvas_height) =
+                get_canvas_size(area.width, area.height - STACKED_PANEL_HEIGHT);
+            let [canvas_row, panel_row] = Layout::vertical([
+                Constraint::Length(canvas_height),
+                Constraint::Length(STACKED_PANEL_HEIGHT),
+            ])
+            .flex(Flex::Center)
+            .areas(area);
+            let [canvas] = Layout::horizontal([Constraint::Length(canvas_width)])
+                .flex(Flex::Center)
+                .areas(canvas_row);
+            let [side, highscores] =
+                Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(panel_row);
+            let [info, collectables] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(side);
+            Some(GameLayout {
+                canvas,
+                side: Some(side),
+                info: Some(info),
+                collectables: Some(collectables),
+                highscores: Some(highscores),
+            })
+        } else {
+            let (canvas_width, canvas_height) = get_canvas_size(area.width, area.height);
+            let [canvas] = Layout::horizontal([Constraint::Length(canvas_width)])
+                .flex(Flex::Center)
+                .areas(area);
+            let [canvas] = Layout::vertical([Constraint::Length(canvas_height)])
+                .flex(Flex::Center)
+                .areas(canvas);
+            Some(GameLayout {
+                canvas,
+                side: None,
+                info: None,
+                collectables: None,
+                highscores: None,
+            })
+        }
+    }
+}
+
+impl StatefulWidget for SnakeGameWidget {
+    type State = App;
+
+    fn render(self, area: Rect, buf: &mut Buffer, app: &mut App) {
+        app.last_area = Some((area.width, area.height));
+        let Some(layout) = Self::get_layout(area, app.field_size) else {
+            render_too_small(area, buf, app.field_size, app.theme.danger);
+            return;
+        };
+        let inner_area = layout.canvas;
+
+        if let Some(highscores_area) = layout.highscores {
+            let mut highscore_scroll = app.highscore_scroll;
+            Highscores::new(app.get_highscores())
+                .title(app.get_highscores_title())
+                .render(highscores_area, buf, &mut highscore_scroll);
+            app.highscore_scroll = highscore_scroll;
+        }
+
+        match app.current_screen {
+            CurrentScreen::Main | CurrentScreen::Lost | CurrentScreen::QuitConfirm => {
+                GameCanvas::new(app).render(inner_area, buf);
+                if matches!(app.current_screen, CurrentScreen::Lost) && !app.is_dying() {
+                    GameOverPopup::new(app).render(inner_area, buf);
+                }
+                if matches!(app.current_screen, CurrentScreen::QuitConfirm) {
+                    QuitDialog::new(app).render(inner_area, buf);
+                }
+                if let Some(info_area) = layout.info {
+                    GameInfo::new(app).render(info_area, buf);
+                }
+                if let Some(collectables_area) = layout.collectables {
+                    CollectablesPanel::new(app).render(collectables_area, buf);
+                }
+            }
+            CurrentScreen::Menu
+            | CurrentScreen::Settings
+            | CurrentScreen::Stats
+            | CurrentScreen::Achievements => {
+                match app.current_screen {
+                    CurrentScreen::Menu => Menu::new(app).render(inner_area, buf),
+                    CurrentScreen::Settings => SettingsMenu::new(app).render(inner_area, buf),
+                    CurrentScreen::Stats => StatsScreen::new(app).render(inner_area, buf),
+                    _ => AchievementsScreen::new(app).render(inner_area, buf),
+                }
+                // Left and right blocks
+                if let Some(side_area) = layout.side {
+                    Block::default()
+                        .borders(ratatui::widgets::Borders::ALL)
+                        .render(side_area, buf);
+                }
+            }
+        }
+        Toasts::new(app).render(area, buf);
+    }
+}
+
+fn render_too_small(area: Rect, buf: &mut Buffer, field_size: (u32, u32), color: Color) {
+    let increase_size_paragraph = Paragraph::new(
+        Line::from(format!("min {} x {}", field_size.0 + 2, field_size.1 + 2)).centered(),
+    )
+    .block(
+        Block::default()
+            .style(Style::new().fg(color))
+            .title(Line::from("The screen is to small"))
+            .borders(Borders::ALL),
+    );
+    let [_, increase_size_block, _] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Max(30),
+            Constraint::Fill(1),
+        ])
+        .areas(
+            Layout::vertical([Constraint::Fill(1), Constraint::Max(3), Constraint::Fill(1)])
+                .split(area)[1],
+        );
+    increase_size_paragraph.render(increase_size_block, buf);
+}
@@ -0,0 +1,119 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Paragraph, Sparkline, Widget},
+};
+
+use crate::{
+    game::App,
+    utils::{enums::DeathCause, helpers::convert_seconds_to_string},
+};
+
+/// Lifetime stats of the player with charts of their death causes and recent scores
+pub struct StatsScreen<'a> {
+    app: &'a App,
+}
+
+impl<'a> StatsScreen<'a> {
+    pub fn new(app: &'a App) -> Self {
+        StatsScreen { app }
+    }
+}
+
+impl Widget for StatsScreen<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = &self.app.theme;
+        let stats_block = Block::default()
+            .title("Statistics")
+            .title_bottom(Line::from("Esc back").centered())
+            .borders(ratatui::widgets::Borders::ALL)
+            .border_type(BorderType::QuadrantInside)
+            .style(Style::default().fg(theme.text));
+        let inner_area = stats_block.inner(area);
+        stats_block.render(area, buf);
+
+        let Some(stats) = self.app.get_lifetime_stats() else {
+            Paragraph::new("No rounds played yet")
+                .centered()
+                .render(inner_area, buf);
+            return;
+        };
+
+        let mut lines = vec![
+            Line::from(format!("Games played: {}", stats.games_played)),
+            Line::from(format!(
+                "Play time: {}",
+                convert_seconds_to_string(&stats.play_time)
+            )),
+            Line::from(format!("Apples eaten: {}", stats.apples_eaten)),
+            Line::from(format!(
+                "Daily streak: {} (best {})",
+                stats.get_current_streak(),
+                stats.best_daily_streak
+            )),
+        ];
+        let mut modes: Vec<_> = stats.modes.iter().collect();
+        modes.sort_by_key(|(mode, _)| mode.get_label());
+        for (mode, mode_stats) in modes {
+            let average = mode_stats.get_average_score();
+            let average = if mode.is_lower_score_better() {
+                mode.format_score(average.round() as i32)
+            } else {
+                format!("{average:.1}")
+            };
+            lines.push(Line::from(format!(
+                "{}: best {} · average {}",
+                mode.get_label(),
+                mode.format_score(mode_stats.best_score),
+                average
+            )));
+        }
+
+        let [summary_area, charts_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner_area);
+        Paragraph::new(lines).render(summary_area, buf);
+
+        let [deaths_area, scores_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(charts_area);
+        let bars: Vec<Bar> = DeathCause::ALL
+            .iter()
+            .map(|cause| {
+                Bar::default()
+                    .value(stats.deaths.get(cause).copied().unwrap_or(0) as u64)
+                    .label(Line::from(cause.get_short_label()))
+            })
+            .collect();
+        BarChart::default()
+            .block(
+                Block::default()
+                    .title("Deaths")
+                    .borders(ratatui::widgets::Borders::ALL),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(theme.danger))
+            .render(deaths_area, buf);
+
+        let scores: Vec<u64> = stats
+            .recent_scores
+            .iter()
+            .map(|score| (*score).max(0) as u64)
+            .collect();
+        Sparkline::default()
+            .block(
+                Block::default()
+                    .title("Recent scores")
+                    .borders(ratatui::widgets::Borders::ALL),
+            )
+            .data(&scores)
+            .style(Style::default().fg(theme.accent))
+            .render(scores_area, buf);
+    }
+}
@@ -0,0 +1,46 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::game::App;
+
+const TOAST_WIDTH: u16 = 30;
+
+/// Notifications stacked in the top right corner of the given area
+pub struct Toasts<'a> {
+    app: &'a App,
+}
+
+impl<'a> Toasts<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Toasts { app }
+    }
+}
+
+impl Widget for Toasts<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = &self.app.theme;
+        let [column] = Layout::horizontal([Constraint::Length(TOAST_WIDTH)])
+            .flex(Flex::End)
+            .areas(area);
+        let toast_areas =
+            Layout::vertical(self.app.toasts.iter().map(|_| Constraint::Length(3))).split(column);
+        for (toast, toast_area) in self.app.toasts.iter().zip(toast_areas.iter()) {
+            Clear.render(*toast_area, buf);
+            Paragraph::new(Line::from(toast.message.clone()).bold())
+                .centered()
+                .style(Style::default().fg(theme.text))
+                .block(
+                    Block::default()
+                        .title(toast.title.clone())
+                        .borders(ratatui::widgets::Borders::ALL)
+                        .border_style(Style::default().fg(theme.accent)),
+                )
+                .render(*toast_area, buf);
+        }
+    }
+}
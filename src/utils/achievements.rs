@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::App,
+    utils::{
+        collectables::{CollectableId, GOLDEN_APPLE, REVERSE},
+        enums::{DeathCause, Turn},
+        game_events::GameEvent,
+        helpers::{get_data_file_path, save_data_file},
+    },
+};
+
+/// What has to happen during a round to unlock an achievement
+pub enum Condition {
+    ScoreAtLeast(i32),
+    SurviveSeconds(u64),
+    /// Collect an item a number of times in one round
+    CollectInRound(CollectableId, u32),
+    /// Eat a number of items that count as apples in one round
+    ApplesInRound(u32),
+    DieBy(DeathCause),
+    /// Reach the next level without a single turn in the given direction
+    LevelWithoutTurn(Turn),
+}
+
+impl Condition {
+    /// Checks the condition right after the event happened
+    pub fn is_met(&self, event: &GameEvent, app: &App) -> bool {
+        match (self, event) {
+            (Condition::ScoreAtLeast(score), _) => {
+                !app.config.game_mode.is_lower_score_better() && app.get_score() >= *score
+            }
+            (Condition::SurviveSeconds(seconds), GameEvent::Second) => app.round_time >= *seconds,
+            (Condition::CollectInRound(collectable_type, count), GameEvent::Collected(_)) => {
+                app.stats.get_collected(collectable_type) >= *count
+            }
+            (Condition::ApplesInRound(count), GameEvent::Collected(_)) => {
+                app.stats.apples_eaten >= *count
+            }
+            (Condition::DieBy(cause), GameEvent::Died(died_by)) => cause == died_by,
+            (Condition::LevelWithoutTurn(turn), GameEvent::LevelUp(level)) => !app
+                .stats
+                .turns
+                .iter()
+                .any(|(turn_level, made_turn)| turn_level + 1 == *level && made_turn == turn),
+            _ => false,
+        }
+    }
+}
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: Condition,
+}
+
+pub static ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_points",
+        name: "First Steps",
+        description: "Score 10 points",
+        condition: Condition::ScoreAtLeast(10),
+    },
+    Achievement {
+        id: "fifty_points",
+        name: "Half a Hundred",
+        description: "Score 50 points",
+        condition: Condition::ScoreAtLeast(50),
+    },
+    Achievement {
+        id: "survivor",
+        name: "Survivor",
+        description: "Survive 5 minutes",
+        condition: Condition::SurviveSeconds(300),
+    },
+    Achievement {
+        id: "hungry",
+        name: "Hungry",
+        description: "Eat 25 apples in one game",
+        condition: Condition::ApplesInRound(25),
+    },
+    Achievement {
+        id: "dizzy",
+        name: "Dizzy",
+        description: "Eat 3 reverse items in one game",
+        condition: Condition::CollectInRound(REVERSE, 3),
+    },
+    Achievement {
+        id: "golden",
+        name: "Golden Touch",
+        description: "Eat a golden apple",
+        condition: Condition::CollectInRound(GOLDEN_APPLE, 1),
+    },
+    Achievement {
+        id: "ouroboros",
+        name: "Ouroboros",
+        description: "Bite your own tail",
+        condition: Condition::DieBy(DeathCause::Tail),
+    },
+    Achievement {
+        id: "right_minded",
+        name: "Right Minded",
+        description: "Finish a level without turning left",
+        condition: Condition::LevelWithoutTurn(Turn::Left),
+    },
+];
+
+/// Unlocked achievements, saved in `achievements.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementStore {
+    /// Unix timestamps of the unlocks by achievement id
+    unlocked: HashMap<String, u64>,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+impl AchievementStore {
+    pub fn load() -> Self {
+        let path = get_data_file_path("achievements.json");
+        let mut store: AchievementStore = match path.and_then(read_to_string) {
+            Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or_default(),
+            Err(_err) => AchievementStore::default(),
+        };
+        store.persistent = true;
+        store
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+
+    pub fn get_unlocked_count(&self) -> usize {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.is_unlocked(achievement.id))
+            .count()
+    }
+
+    /// Unlocks the achievement and saves the store, it stays unlocked even if saving failed
+    pub fn unlock(&mut self, id: &str) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        self.unlocked.insert(id.to_string(), timestamp);
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if !self.persistent {
+            return Ok(());
+        }
+        save_data_file("achievements.json", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::collectables::APPLE;
+    use crate::utils::scores::ScoreManager;
+
+    fn create_app() -> App {
+        App::with_score_manager(ScoreManager::in_memory())
+    }
+
+    #[test]
+    fn score_is_checked_after_every_event() {
+        let mut app = create_app();
+        let condition = Condition::ScoreAtLeast(10);
+        app.scoring.total = 9;
+        assert!(!condition.is_met(&GameEvent::Second, &app));
+        app.scoring.total = 10;
+        assert!(condition.is_met(&GameEvent::Second, &app));
+        assert!(condition.is_met(&GameEvent::Collected(APPLE), &app));
+    }
+
+    #[test]
+    fn round_time_is_checked_every_second() {
+        let mut app = create_app();
+        let condition = Condition::SurviveSeconds(300);
+        app.round_time = 299;
+        assert!(!condition.is_met(&GameEvent::Second, &app));
+        app.round_time = 300;
+        assert!(condition.is_met(&GameEvent::Second, &app));
+        assert!(!condition.is_met(&GameEvent::Collected(APPLE), &app));
+    }
+
+    #[test]
+    fn collected_items_are_counted_per_type() {
+        let mut app = create_app();
+        let condition = Condition::CollectInRound(REVERSE, 3);
+        for collectable_type in [REVERSE, REVERSE, GOLDEN_APPLE] {
+            let event = GameEvent::Collected(collectable_type);
+            app.stats.on_event(&event, 1, &app.registry);
+        }
+        let event = GameEvent::Collected(REVERSE);
+        assert!(!condition.is_met(&event, &app));
+        app.stats.on_event(&event, 1, &app.registry);
+        assert!(condition.is_met(&event, &app));
+        assert!(!condition.is_met(&GameEvent::Second, &app));
+    }
+
+    #[test]
+    fn golden_apples_count_as_apples() {
+        let mut app = create_app();
+        let condition = Condition::ApplesInRound(3);
+        for collectable_type in [APPLE, GOLDEN_APPLE, REVERSE] {
+            let event = GameEvent::Collected(collectable_type);
+            app.stats.on_event(&event, 1, &app.registry);
+        }
+        let event = GameEvent::Collected(GOLDEN_APPLE);
+        assert!(!condition.is_met(&event, &app));
+        app.stats.on_event(&event, 1, &app.registry);
+        assert!(condition.is_met(&event, &app));
+        assert_eq!(app.stats.get_collected(APPLE), 1);
+        assert_eq!(app.stats.get_collected(GOLDEN_APPLE), 2);
+    }
+
+    #[test]
+    fn death_cause_has_to_match() {
+        let app = create_app();
+        let condition = Condition::DieBy(DeathCause::Tail);
+        assert!(condition.is_met(&GameEvent::Died(DeathCause::Tail), &app));
+        assert!(!condition.is_met(&GameEvent::Died(DeathCause::Wall), &app));
+    }
+
+    #[test]
+    fn only_turns_of_the_finished_level_count() {
+        let mut app = create_app();
+        let condition = Condition::LevelWithoutTurn(Turn::Left);
+        app.stats.turns = vec![(1, Turn::Right), (2, Turn::Left)];
+        assert!(condition.is_met(&GameEvent::LevelUp(2), &app));
+        assert!(!condition.is_met(&GameEvent::LevelUp(3), &app));
+        app.stats.turns.push((1, Turn::Left));
+        assert!(!condition.is_met(&GameEvent::LevelUp(2), &app));
+    }
+}
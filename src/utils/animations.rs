@@ -0,0 +1,80 @@
+use crate::utils::collectables::CollectableId;
+
+/// A game tick lasts 50 milliseconds
+pub const TICKS_PER_SECOND: u32 = 20;
+/// Game ticks of the countdown before a round starts
+pub const COUNTDOWN_TICKS: u32 = 3 * TICKS_PER_SECOND;
+pub const FLASH_TICKS: u32 = 6;
+pub const SCORE_POPUP_TICKS: u32 = 20;
+pub const DEATH_TICKS: u32 = 30;
+
+pub enum AnimationKind {
+    /// Lights up the cells around a collected item
+    Flash(CollectableId),
+    /// Shows the change of the score rising up from where it happened
+    ScorePopup(i32),
+    /// Dissolves the snake from the tail to the head
+    Death,
+}
+
+/// A short visual effect that runs for a number of game ticks
+pub struct Animation {
+    pub kind: AnimationKind,
+    pub position: (f64, f64),
+    duration: u32,
+    remaining_ticks: u32,
+}
+
+impl Animation {
+    pub fn new(kind: AnimationKind, position: (f64, f64), duration: u32) -> Self {
+        Animation {
+            kind,
+            position,
+            duration,
+            remaining_ticks: duration,
+        }
+    }
+
+    /// Should be called on every game tick
+    ///
+    /// Returns true if the animation is over
+    pub fn on_tick(&mut self) -> bool {
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        self.remaining_ticks == 0
+    }
+
+    /// Returns how far the animation has run as a ratio between 0.0 and 1.0
+    pub fn get_progress(&self) -> f64 {
+        if self.duration == 0 {
+            return 1.0;
+        }
+        1.0 - self.remaining_ticks as f64 / self.duration as f64
+    }
+}
+
+pub const TOAST_TICKS: u32 = 3 * TICKS_PER_SECOND;
+
+/// A short notification in the corner of the screen
+pub struct Toast {
+    pub title: String,
+    pub message: String,
+    remaining_ticks: u32,
+}
+
+impl Toast {
+    pub fn new(title: String, message: String) -> Self {
+        Toast {
+            title,
+            message,
+            remaining_ticks: TOAST_TICKS,
+        }
+    }
+
+    /// Should be called on every game tick
+    ///
+    /// Returns true if the toast should disappear
+    pub fn on_tick(&mut self) -> bool {
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        self.remaining_ticks == 0
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// Seconds between two steps of the walls closing in
+pub const SHRINK_INTERVAL: u64 = 15;
+/// Seconds before a step in which the next border is shown as a warning
+pub const SHRINK_WARNING: u64 = 3;
+/// The walls stop closing in once the play area has this size
+const MIN_ARENA_SIZE: (u32, u32) = (10, 5);
+
+/// Play area of the Shrinking Arena mode, the walls move in by one cell every step
+///
+/// Cells are half as high as they are wide, so vertically the walls move in by half a unit
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Arena {
+    /// Cells the walls moved in from every edge of the field
+    pub inset: u32,
+    max_inset: u32,
+    seconds_until_shrink: u64,
+}
+
+impl Arena {
+    pub fn new(field_size: (u32, u32)) -> Self {
+        Arena {
+            inset: 0,
+            max_inset: (field_size.0.saturating_sub(MIN_ARENA_SIZE.0) / 2)
+                .min(field_size.1.saturating_sub(MIN_ARENA_SIZE.1)),
+            seconds_until_shrink: SHRINK_INTERVAL,
+        }
+    }
+
+    /// Counts down to the next step and returns whether the walls moved in
+    pub fn on_second_update(&mut self) -> bool {
+        if self.inset >= self.max_inset {
+            return false;
+        }
+        self.seconds_until_shrink -= 1;
+        if self.seconds_until_shrink > 0 {
+            return false;
+        }
+        self.inset += 1;
+        self.seconds_until_shrink = SHRINK_INTERVAL;
+        true
+    }
+
+    /// Returns whether the walls move in within the next few seconds
+    pub fn is_shrinking_soon(&self) -> bool {
+        self.inset < self.max_inset && self.seconds_until_shrink <= SHRINK_WARNING
+    }
+
+    /// Returns the seconds until the walls move in, `None` once they stopped
+    pub fn get_seconds_until_shrink(&self) -> Option<u64> {
+        (self.inset < self.max_inset).then_some(self.seconds_until_shrink)
+    }
+
+    /// Returns the cells along the edge of the play area of the inset,
+    /// which are the first to be closed off by the next step
+    pub fn get_border(inset: u32, field_size: (u32, u32)) -> Vec<(f64, f64)> {
+        let (width, height) = field_size;
+        let mut cells = vec![];
+        for x in (inset + 1)..=width.saturating_sub(inset) {
+            for half_row in inset..(2 * height).saturating_sub(inset) {
+                let position = (x as f64, half_row as f64 * 0.5);
+                if !is_inside(position, inset + 1, field_size) {
+                    cells.push(position);
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Returns whether the position is inside the field after the walls moved in by the inset
+pub fn is_inside((x, y): (f64, f64), inset: u32, (width, height): (u32, u32)) -> bool {
+    let (inset_x, inset_y) = (inset as f64, inset as f64 * 0.5);
+    x > inset_x && x <= width as f64 - inset_x && y >= inset_y && y < height as f64 - inset_y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walls_move_in_every_interval() {
+        let mut arena = Arena::new((50, 25));
+        for _ in 1..SHRINK_INTERVAL {
+            assert!(!arena.on_second_update());
+        }
+        assert!(arena.on_second_update());
+        assert_eq!(arena.inset, 1);
+        assert_eq!(arena.get_seconds_until_shrink(), Some(SHRINK_INTERVAL));
+        assert!(!arena.is_shrinking_soon());
+    }
+
+    #[test]
+    fn walls_stop_at_the_minimum_size() {
+        let field_size = (50, 25);
+        let mut arena = Arena::new(field_size);
+        let steps = (0..SHRINK_INTERVAL * 100)
+            .filter(|_| arena.on_second_update())
+            .count() as u32;
+        assert_eq!(steps, arena.inset);
+        assert_eq!(arena.get_seconds_until_shrink(), None);
+        let width = field_size.0 - 2 * arena.inset;
+        // A cell is half a unit high, so the height loses one unit per step
+        let height = field_size.1 - arena.inset;
+        assert!(width >= MIN_ARENA_SIZE.0 && height >= MIN_ARENA_SIZE.1);
+    }
+
+    #[test]
+    fn every_step_closes_off_one_cell_on_each_side() {
+        let field_size = (50, 25);
+        assert!(is_inside((1.0, 0.0), 0, field_size));
+        assert!(!is_inside((1.0, 0.0), 1, field_size));
+        assert!(is_inside((2.0, 0.5), 1, field_size));
+        assert!(is_inside((49.0, 24.0), 1, field_size));
+        assert!(!is_inside((49.0, 24.5), 1, field_size));
+
+        // The ring around the play area: two rows of 50 cells and two columns of 48
+        let border = Arena::get_border(0, field_size);
+        assert_eq!(border.len(), 2 * 50 + 2 * 48);
+        assert!(border.contains(&(1.0, 24.5)));
+        assert!(!border.contains(&(2.0, 1.0)));
+        assert_eq!(Arena::get_border(1, field_size).len(), 2 * 48 + 2 * 46);
+    }
+}
@@ -1,36 +1,62 @@
+use rand::Rng;
+
 use crate::{
     game::App,
-    utils::helpers::{get_direction_from_vector, get_directionvector_from_snake},
+    utils::{
+        enums::DeathCause,
+        helpers::{get_direction_from_vector, get_directionvector_from_snake},
+    },
 };
 
-#[derive(Clone)]
-pub enum CollectableType {
-    Apple,
-    Speed,
-    Reverse,
-}
+/// Identifies a kind of collectable in the [`CollectableRegistry`](crate::utils::registry::CollectableRegistry)
+pub type CollectableId = &'static str;
 
-impl CollectableType {
-    pub fn from_random_special() -> Self {
-        if rand::random::<bool>() {
-            CollectableType::Reverse
-        } else {
-            CollectableType::Speed
-        }
-    }
+pub const APPLE: CollectableId = "apple";
+pub const SPEED: CollectableId = "speed";
+pub const REVERSE: CollectableId = "reverse";
+pub const SLOW: CollectableId = "slow";
+pub const GOLDEN_APPLE: CollectableId = "golden_apple";
+pub const SHRINK: CollectableId = "shrink";
+pub const GHOST: CollectableId = "ghost";
+pub const MAGNET: CollectableId = "magnet";
+pub const BOMB: CollectableId = "bomb";
+pub const PORTAL: CollectableId = "portal";
+pub const SHIELD: CollectableId = "shield";
+
+/// What happens after the head ran into an item
+pub enum CollectResult {
+    /// The item stays on the field
+    Keep,
+    Remove,
+    /// The item is removed and the snake dies, unless something protects it
+    Deadly(DeathCause),
 }
 
 pub trait Collectable {
-    fn new(x: f64, y: f64) -> Self;
+    fn new(x: f64, y: f64) -> Self
+    where
+        Self: Sized;
     fn get_position(&self) -> (f64, f64);
+    /// Returns every cell the item occupies on the field
+    fn get_positions(&self) -> Vec<(f64, f64)> {
+        vec![self.get_position()]
+    }
+    /// Restores every cell of an item that occupies more than one, like a saved portal pair
+    fn set_positions(&mut self, _positions: &[(f64, f64)]) {}
+    /// Called once right after the item was placed on the field
+    fn on_spawn(&mut self, _app: &mut App) {}
+    /// Should be called on every game update
+    ///
+    /// Returns true if the item should be removed from the game
     fn on_game_update(&mut self, _app: &mut App) -> bool {
-        return false;
+        false
     }
-    fn on_collect(&mut self, app: &mut App) -> bool;
+    /// Should be called when the player collects the item
+    fn on_collect(&mut self, app: &mut App) -> CollectResult;
+    /// Should be called every second
     fn on_second_update(&mut self) {}
-    fn is_visible(&self) -> bool {
-        return true;
-    }
+    /// Called while a magnet is active, items that can be pulled move one cell closer
+    fn move_towards(&mut self, _target: (f64, f64)) {}
 }
 
 pub struct AppleCollectable {
@@ -44,64 +70,78 @@ impl Collectable for AppleCollectable {
     fn get_position(&self) -> (f64, f64) {
         self.position
     }
-    fn on_collect(&mut self, app: &mut App) -> bool {
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
         app.increase_lenght();
-        app.spawn_item(CollectableType::Apple);
-        return true;
+        app.spawn_item(APPLE);
+        CollectResult::Remove
+    }
+    fn move_towards(&mut self, target: (f64, f64)) {
+        self.position = get_step_towards(self.position, target);
     }
 }
 
-pub struct SpeedCollectable {
+pub struct GoldenAppleCollectable {
     position: (f64, f64),
-    remaining_time: Option<u32>,
 }
 
-impl SpeedCollectable {
-    pub fn get_remaining_time(&self) -> Option<u32> {
-        self.remaining_time
+impl Collectable for GoldenAppleCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        GoldenAppleCollectable { position: (x, y) }
+    }
+
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        for _ in 0..3 {
+            app.increase_lenght();
+        }
+        CollectResult::Remove
     }
+
+    fn move_towards(&mut self, target: (f64, f64)) {
+        self.position = get_step_towards(self.position, target);
+    }
+}
+
+pub struct SpeedCollectable {
+    position: (f64, f64),
 }
 
 impl Collectable for SpeedCollectable {
     fn new(x: f64, y: f64) -> Self {
-        SpeedCollectable {
-            position: (x, y),
-            remaining_time: None,
-        }
+        SpeedCollectable { position: (x, y) }
     }
 
     fn get_position(&self) -> (f64, f64) {
         self.position
     }
 
-    fn on_game_update(&mut self, app: &mut App) -> bool {
-        if let Some(remaining_time) = self.remaining_time {
-            if remaining_time > 0 {
-                return false;
-            } else {
-                app.game_speed -= 1;
-                return true;
-            }
-        }
-        false
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        let duration = app.rng.random_range(10..20);
+        app.add_effect(SPEED, duration);
+        CollectResult::Remove
     }
+}
 
-    fn on_collect(&mut self, app: &mut App) -> bool {
-        app.game_speed += 1;
-        self.remaining_time = Some(rand::random_range(10..20));
-        false
+pub struct SlowCollectable {
+    position: (f64, f64),
+}
+
+impl Collectable for SlowCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        SlowCollectable { position: (x, y) }
     }
 
-    fn on_second_update(&mut self) {
-        if let Some(remaining_time) = self.remaining_time {
-            if remaining_time > 0 {
-                self.remaining_time = Some(remaining_time - 1);
-            }
-        }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
     }
 
-    fn is_visible(&self) -> bool {
-        self.remaining_time.is_none()
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        let duration = app.rng.random_range(8..15);
+        app.add_effect(SLOW, duration);
+        CollectResult::Remove
     }
 }
 
@@ -116,73 +156,186 @@ impl Collectable for ReverseCollectable {
     fn get_position(&self) -> (f64, f64) {
         self.position
     }
-    fn on_collect(&mut self, app: &mut App) -> bool {
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
         app.snake.reverse();
         let vector: (f64, f64) = get_directionvector_from_snake(&app.snake);
         app.direction = get_direction_from_vector(&vector);
-        true
+        CollectResult::Remove
     }
 }
 
-pub enum AnyCollectable {
-    Apple(AppleCollectable),
-    Speed(SpeedCollectable),
-    Reverse(ReverseCollectable),
+pub struct ShrinkCollectable {
+    position: (f64, f64),
 }
 
-impl AnyCollectable {
-    pub fn new(x: f64, y: f64, collectable_type: CollectableType) -> Self {
-        match collectable_type {
-            CollectableType::Apple => AnyCollectable::Apple(AppleCollectable::new(x, y)),
-            CollectableType::Speed => AnyCollectable::Speed(SpeedCollectable::new(x, y)),
-            CollectableType::Reverse => AnyCollectable::Reverse(ReverseCollectable::new(x, y)),
-        }
+impl Collectable for ShrinkCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        ShrinkCollectable { position: (x, y) }
     }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        let new_length = app.snake.len().saturating_sub(3).max(3);
+        app.snake.truncate(new_length);
+        CollectResult::Remove
+    }
+}
 
-    pub fn get_position(&self) -> (f64, f64) {
-        match self {
-            AnyCollectable::Apple(a) => a.get_position(),
-            AnyCollectable::Speed(s) => s.get_position(),
-            AnyCollectable::Reverse(r) => r.get_position(),
-        }
+pub struct GhostCollectable {
+    position: (f64, f64),
+}
+
+impl Collectable for GhostCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        GhostCollectable { position: (x, y) }
+    }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        let duration = app.rng.random_range(5..10);
+        app.add_effect(GHOST, duration);
+        CollectResult::Remove
+    }
+}
+
+pub struct MagnetCollectable {
+    position: (f64, f64),
+}
+
+impl Collectable for MagnetCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        MagnetCollectable { position: (x, y) }
+    }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        let duration = app.rng.random_range(10..20);
+        app.add_effect(MAGNET, duration);
+        CollectResult::Remove
     }
+}
 
-    /// Should be called on every game update
-    ///
-    /// Returns true if the item should be removed from the game
-    pub fn on_game_update(&mut self, app: &mut App) -> bool {
-        match self {
-            AnyCollectable::Apple(a) => a.on_game_update(app),
-            AnyCollectable::Speed(s) => s.on_game_update(app),
-            AnyCollectable::Reverse(r) => r.on_game_update(app),
-        }
+pub struct BombCollectable {
+    position: (f64, f64),
+}
+
+impl Collectable for BombCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        BombCollectable { position: (x, y) }
+    }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
     }
+    fn on_collect(&mut self, _app: &mut App) -> CollectResult {
+        CollectResult::Deadly(DeathCause::Obstacle)
+    }
+}
 
-    /// Should be called every second
-    pub fn on_second_update(&mut self) {
-        match self {
-            AnyCollectable::Apple(a) => a.on_second_update(),
-            AnyCollectable::Speed(s) => s.on_second_update(),
-            AnyCollectable::Reverse(r) => r.on_second_update(),
+pub struct PortalCollectable {
+    position: (f64, f64),
+    exit: (f64, f64),
+}
+
+impl Collectable for PortalCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        PortalCollectable {
+            position: (x, y),
+            exit: (x, y),
         }
     }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+    fn get_positions(&self) -> Vec<(f64, f64)> {
+        vec![self.position, self.exit]
+    }
+    fn set_positions(&mut self, positions: &[(f64, f64)]) {
+        if let Some(exit) = positions.get(1) {
+            self.exit = *exit;
+        }
+    }
+    fn on_spawn(&mut self, app: &mut App) {
+        // The portal is not on the field yet, so the free cells still include its entrance
+        let mut free_positions = app.get_free_positions();
+        free_positions.retain(|position| *position != self.position);
+        if let Some(exit) = app.choose_position(&free_positions) {
+            self.exit = exit;
+        }
+    }
+    /// Teleports the head to the other end of the portal pair
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        app.snake[0] = if app.snake[0] == self.position {
+            self.exit
+        } else {
+            self.position
+        };
+        CollectResult::Remove
+    }
+}
 
-    /// Should be called when the player collects the item
-    ///
-    /// Returns true if the item should be removed from the game
-    pub fn on_collect(&mut self, app: &mut App) -> bool {
-        match self {
-            AnyCollectable::Apple(a) => a.on_collect(app),
-            AnyCollectable::Speed(s) => s.on_collect(app),
-            AnyCollectable::Reverse(r) => r.on_collect(app),
+pub struct ShieldCollectable {
+    position: (f64, f64),
+}
+
+impl Collectable for ShieldCollectable {
+    fn new(x: f64, y: f64) -> Self {
+        ShieldCollectable { position: (x, y) }
+    }
+    fn get_position(&self) -> (f64, f64) {
+        self.position
+    }
+    fn on_collect(&mut self, app: &mut App) -> CollectResult {
+        app.add_effect(SHIELD, 30);
+        CollectResult::Remove
+    }
+}
+
+/// Returns the position one cell closer to the target
+fn get_step_towards(position: (f64, f64), target: (f64, f64)) -> (f64, f64) {
+    let (x, y) = position;
+    if x != target.0 {
+        (x + (target.0 - x).signum(), y)
+    } else if y != target.1 {
+        (x, y + (target.1 - y).signum() * 0.5)
+    } else {
+        position
+    }
+}
+
+/// An item on the field together with the time it has left before it disappears
+pub struct SpawnedCollectable {
+    pub collectable_type: CollectableId,
+    pub collectable: Box<dyn Collectable>,
+    pub despawn_timer: Option<u32>,
+}
+
+impl SpawnedCollectable {
+    pub fn new(
+        collectable_type: CollectableId,
+        collectable: Box<dyn Collectable>,
+        despawn_timer: Option<u32>,
+    ) -> Self {
+        SpawnedCollectable {
+            collectable_type,
+            collectable,
+            despawn_timer,
         }
     }
 
-    pub fn is_visible(&self) -> bool {
-        match self {
-            AnyCollectable::Apple(a) => a.is_visible(),
-            AnyCollectable::Speed(s) => s.is_visible(),
-            AnyCollectable::Reverse(r) => r.is_visible(),
+    /// Counts down the despawn timer
+    ///
+    /// Returns true if the item should be removed from the game
+    pub fn on_second_update(&mut self) -> bool {
+        self.collectable.on_second_update();
+        match self.despawn_timer.as_mut() {
+            Some(timer) => {
+                *timer = timer.saturating_sub(1);
+                *timer == 0
+            }
+            None => false,
         }
     }
 }
@@ -0,0 +1,191 @@
+use std::io;
+use std::ops::RangeInclusive;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    enums::GameMode,
+    registry::{CollectableDefinition, ItemKind},
+    spawn_table::SpawnRule,
+};
+
+/// Field widths a round can be played on, the auto field size stays within them
+pub const FIELD_WIDTHS: RangeInclusive<u32> = 20..=120;
+/// Field heights a round can be played on, the auto field size stays within them
+pub const FIELD_HEIGHTS: RangeInclusive<u32> = 10..=60;
+
+/// Which special items show up more often
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ItemMix {
+    #[default]
+    Normal,
+    /// Every item except hazards is twice as likely
+    PowerUps,
+    /// Hazards like bombs are three times as likely and more of them fit on the field
+    Hazards,
+    /// Apples that spawn as special items, like golden apples, are four times as likely
+    Golden,
+}
+
+impl ItemMix {
+    pub const ALL: [ItemMix; 4] = [
+        ItemMix::Normal,
+        ItemMix::PowerUps,
+        ItemMix::Hazards,
+        ItemMix::Golden,
+    ];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ItemMix::Normal => "Normal items",
+            ItemMix::PowerUps => "More power-ups",
+            ItemMix::Hazards => "More bombs",
+            ItemMix::Golden => "Golden apples",
+        }
+    }
+
+    /// Changes the spawn rule of an item to match the mix
+    pub fn apply(&self, definition: &CollectableDefinition, rule: &mut SpawnRule) {
+        match (self, definition.kind) {
+            (ItemMix::PowerUps, kind) if kind != ItemKind::Hazard => rule.weight *= 2,
+            (ItemMix::Hazards, ItemKind::Hazard) => {
+                rule.weight *= 3;
+                rule.max_on_board += 2;
+            }
+            (ItemMix::Golden, ItemKind::Apple) => rule.weight *= 4,
+            _ => {}
+        }
+    }
+}
+
+/// Changes to the usual rules of a round
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Modifiers {
+    /// The snake leaves the field on one side and comes back on the other
+    pub open_walls: bool,
+    pub item_mix: ItemMix,
+}
+
+/// Everything that decides how a round plays out, so the same round can be played again
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub game_mode: GameMode,
+    pub field_size: (u32, u32),
+    /// Seed of the random number generator of the round
+    pub seed: u64,
+    #[serde(default)]
+    pub modifiers: Modifiers,
+    /// Date of the daily challenge in the format "YYYY-MM-DD"
+    #[serde(default)]
+    pub challenge_date: Option<String>,
+}
+
+impl GameConfig {
+    /// Creates a config with a random seed
+    pub fn new(game_mode: GameMode, field_size: (u32, u32)) -> Self {
+        GameConfig {
+            game_mode,
+            field_size,
+            seed: rand::random(),
+            modifiers: Modifiers::default(),
+            challenge_date: None,
+        }
+    }
+
+    /// Creates the daily challenge of the date, which is the same for everyone
+    pub fn daily(date: &str) -> Self {
+        let seed = get_seed_from_text(date);
+        // Unlike StdRng, ChaCha12Rng keeps its output across versions of rand
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let field_sizes = [(40, 20), (50, 25), (60, 30)];
+        GameConfig {
+            game_mode: GameMode::Daily,
+            field_size: field_sizes[rng.random_range(0..field_sizes.len())],
+            seed,
+            modifiers: Modifiers {
+                open_walls: rng.random_bool(0.5),
+                item_mix: ItemMix::ALL[rng.random_range(0..ItemMix::ALL.len())],
+            },
+            challenge_date: Some(date.to_string()),
+        }
+    }
+
+    /// Returns the config for another round with the same settings,
+    /// only daily challenges keep their board
+    pub fn for_retry(&self) -> Self {
+        if self.challenge_date.is_some() {
+            return self.clone();
+        }
+        GameConfig {
+            seed: rand::random(),
+            ..self.clone()
+        }
+    }
+
+    /// Checks a config that was loaded from a file, the game itself only creates valid ones
+    pub fn validate(&self) -> io::Result<()> {
+        let (width, height) = self.field_size;
+        if !FIELD_WIDTHS.contains(&width) || !FIELD_HEIGHTS.contains(&height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the field size {}x{} is not supported", width, height),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig::new(GameMode::Classic, (50, 25))
+    }
+}
+
+/// FNV-1a hash, which unlike the hasher of the standard library is stable between builds
+fn get_seed_from_text(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_challenge_is_the_same_board_for_the_same_date() {
+        let first = GameConfig::daily("2024-01-01");
+        let second = GameConfig::daily("2024-01-01");
+        assert_eq!(first.seed, second.seed);
+        assert_eq!(first.field_size, second.field_size);
+        assert_eq!(first.modifiers.open_walls, second.modifiers.open_walls);
+        assert!(first.modifiers.item_mix == second.modifiers.item_mix);
+        assert_eq!(first.challenge_date.as_deref(), Some("2024-01-01"));
+
+        assert_ne!(GameConfig::daily("2024-01-02").seed, first.seed);
+        assert_eq!(first.for_retry().seed, first.seed);
+    }
+
+    #[test]
+    fn only_field_sizes_of_the_game_are_valid() {
+        assert!(GameConfig::daily("2024-01-01").validate().is_ok());
+        assert!(
+            GameConfig::new(GameMode::Classic, (20, 10))
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            GameConfig::new(GameMode::Classic, (120, 60))
+                .validate()
+                .is_ok()
+        );
+        for field_size in [(0, 0), (50, 0), (19, 25), (121, 25), (50, 61)] {
+            let config = GameConfig::new(GameMode::Classic, field_size);
+            let err = config.validate().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{game::App, utils::collectables::CollectableId};
+
+/// Decides what happens when an effect is picked up while
+/// an effect of the same type is still active
+#[derive(Clone, Copy)]
+pub enum StackRule {
+    /// Every pickup adds its own instance, up to the given amount
+    Stack(usize),
+    /// The active instance starts over with the new duration
+    Refresh,
+    /// The new duration is added on top of the active instance
+    Extend,
+}
+
+type EffectHook = fn(&mut App);
+
+/// How an effect behaves, registered on the item that grants it
+#[derive(Clone, Copy)]
+pub struct EffectDefinition {
+    pub stack_rule: StackRule,
+    on_apply: Option<EffectHook>,
+    on_expire: Option<EffectHook>,
+}
+
+impl EffectDefinition {
+    pub fn new(stack_rule: StackRule) -> Self {
+        EffectDefinition {
+            stack_rule,
+            on_apply: None,
+            on_expire: None,
+        }
+    }
+
+    /// Sets the hook that is called once for every new instance of the effect
+    pub fn on_apply(mut self, hook: EffectHook) -> Self {
+        self.on_apply = Some(hook);
+        self
+    }
+
+    /// Sets the hook that is called once when an instance of the effect runs out
+    pub fn on_expire(mut self, hook: EffectHook) -> Self {
+        self.on_expire = Some(hook);
+        self
+    }
+
+    pub fn apply(&self, app: &mut App) {
+        if let Some(hook) = self.on_apply {
+            hook(app);
+        }
+    }
+
+    pub fn expire(&self, app: &mut App) {
+        if let Some(hook) = self.on_expire {
+            hook(app);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ActiveEffect {
+    /// Id of the item that granted the effect
+    pub effect_type: CollectableId,
+    pub duration: u32,
+    pub remaining_time: u32,
+}
+
+impl ActiveEffect {
+    pub fn new(effect_type: CollectableId, duration: u32) -> Self {
+        ActiveEffect {
+            effect_type,
+            duration,
+            remaining_time: duration,
+        }
+    }
+
+    /// Returns the remaining time as a ratio between 0.0 and 1.0
+    pub fn get_progress(&self) -> f64 {
+        if self.duration == 0 {
+            return 0.0;
+        }
+        self.remaining_time as f64 / self.duration as f64
+    }
+}
@@ -1,9 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::helpers::convert_ticks_to_string;
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum CurrentScreen {
     Main,
     Menu,
     Lost,
+    Settings,
+    Stats,
+    Achievements,
+    /// Asks what happens to the running round before the game is closed, the round is paused
+    QuitConfirm,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MenuEntry {
+    /// Only shown while there is a round that was saved on quit
+    Continue,
+    StartGame,
+    DailyChallenge,
+    Settings,
+    Stats,
+    Achievements,
+    Quit,
+}
+
+impl MenuEntry {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            MenuEntry::Continue => "Continue",
+            MenuEntry::StartGame => "Start Game",
+            MenuEntry::DailyChallenge => "Daily Challenge",
+            MenuEntry::Settings => "Settings",
+            MenuEntry::Stats => "Statistics",
+            MenuEntry::Achievements => "Achievements",
+            MenuEntry::Quit => "Quit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SettingsEntry {
+    FieldSize,
+    Theme,
+    RenderStyle,
+    SnakeSkin,
+    GameMode,
+    Sound,
+    Volume,
+}
+
+impl SettingsEntry {
+    pub const ALL: [SettingsEntry; 7] = [
+        SettingsEntry::GameMode,
+        SettingsEntry::FieldSize,
+        SettingsEntry::Theme,
+        SettingsEntry::RenderStyle,
+        SettingsEntry::SnakeSkin,
+        SettingsEntry::Sound,
+        SettingsEntry::Volume,
+    ];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            SettingsEntry::FieldSize => "Field size",
+            SettingsEntry::Theme => "Theme",
+            SettingsEntry::RenderStyle => "Render style",
+            SettingsEntry::SnakeSkin => "Snake skin",
+            SettingsEntry::GameMode => "Game mode",
+            SettingsEntry::Sound => "Sound",
+            SettingsEntry::Volume => "Volume",
+        }
+    }
+}
+
+/// How the cells of the playing field are drawn
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RenderStyle {
+    #[default]
+    HalfBlock,
+    Braille,
+    FullBlock,
+    /// Half blocks for the snake, the glyph of their definition for collectables
+    Glyphs,
+}
+
+impl RenderStyle {
+    pub const ALL: [RenderStyle; 4] = [
+        RenderStyle::HalfBlock,
+        RenderStyle::Braille,
+        RenderStyle::FullBlock,
+        RenderStyle::Glyphs,
+    ];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            RenderStyle::HalfBlock => "Half blocks",
+            RenderStyle::Braille => "Braille",
+            RenderStyle::FullBlock => "Full blocks",
+            RenderStyle::Glyphs => "Glyphs",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SnakeSkin {
+    #[default]
+    Classic,
+    /// The body fades out towards the tail
+    Gradient,
+    /// Head and tail are drawn as arrows pointing in their direction
+    Directional,
+}
+
+impl SnakeSkin {
+    pub const ALL: [SnakeSkin; 3] = [
+        SnakeSkin::Classic,
+        SnakeSkin::Gradient,
+        SnakeSkin::Directional,
+    ];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            SnakeSkin::Classic => "Classic",
+            SnakeSkin::Gradient => "Gradient",
+            SnakeSkin::Directional => "Directional",
+        }
+    }
+}
+
+/// Actions of the game over screen
+#[derive(Clone, Copy, PartialEq)]
+pub enum LostEntry {
+    Retry,
+    SaveReplay,
+    Menu,
+}
+
+impl LostEntry {
+    pub const ALL: [LostEntry; 3] = [LostEntry::Retry, LostEntry::SaveReplay, LostEntry::Menu];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            LostEntry::Retry => "Retry",
+            LostEntry::SaveReplay => "Save Replay",
+            LostEntry::Menu => "Menu",
+        }
+    }
+}
+
+/// Actions of the dialog shown when the game is closed during a round
+#[derive(Clone, Copy, PartialEq)]
+pub enum QuitEntry {
+    Resume,
+    /// Only offered if the game keeps its data on disk
+    SaveAndLeave,
+    /// Ends the round like a death, so it counts for the leaderboard and stats
+    RecordAndLeave,
+    DiscardAndLeave,
 }
 
+impl QuitEntry {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            QuitEntry::Resume => "Resume",
+            QuitEntry::SaveAndLeave => "Save & Leave",
+            QuitEntry::RecordAndLeave => "Record & Leave",
+            QuitEntry::DiscardAndLeave => "Discard & Leave",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeathCause {
+    Wall,
+    /// The snake ran into its own body
+    Tail,
+    /// Anything placed on the field, like a bomb
+    Obstacle,
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 3] = [DeathCause::Wall, DeathCause::Tail, DeathCause::Obstacle];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            DeathCause::Wall => "Hit the wall",
+            DeathCause::Tail => "Bit its own tail",
+            DeathCause::Obstacle => "Hit an obstacle",
+        }
+    }
+
+    pub fn get_short_label(&self) -> &'static str {
+        match self {
+            DeathCause::Wall => "Wall",
+            DeathCause::Tail => "Tail",
+            DeathCause::Obstacle => "Obstacle",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Down,
@@ -11,7 +209,139 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Returns the step of one cell into the direction, rows are half as high as columns
+    pub fn get_vector(&self) -> (f64, f64) {
+        match self {
+            Direction::Up => (0.0, 0.5),
+            Direction::Down => (0.0, -0.5),
+            Direction::Left => (-1.0, 0.0),
+            Direction::Right => (1.0, 0.0),
+        }
+    }
+
+    pub fn get_opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Returns which way the snake turns when it changes from this direction to the other
+    pub fn get_turn_to(&self, other: Direction) -> Option<Turn> {
+        match (self, other) {
+            (Direction::Up, Direction::Right)
+            | (Direction::Right, Direction::Down)
+            | (Direction::Down, Direction::Left)
+            | (Direction::Left, Direction::Up) => Some(Turn::Right),
+            (Direction::Up, Direction::Left)
+            | (Direction::Left, Direction::Down)
+            | (Direction::Down, Direction::Right)
+            | (Direction::Right, Direction::Up) => Some(Turn::Left),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
 pub enum Event {
     Input(crossterm::event::KeyEvent),
     GameTick,
 }
+
+/// Length of a Time Attack round in seconds
+pub const TIME_ATTACK_SECONDS: u64 = 120;
+/// Apples to eat in an Apple Rush round
+pub const APPLE_RUSH_GOAL: u32 = 30;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Survive as long as possible, scored by the length of the snake
+    #[default]
+    Classic,
+    /// Same board for everyone on the same day
+    Daily,
+    /// Eat as many apples as possible before the time runs out
+    TimeAttack,
+    /// Eat a number of apples as fast as possible, scored by the game ticks it took
+    AppleRush,
+    /// Survive between hazards that wander the field and get faster over time
+    Survival,
+    /// Survive while the walls close in from the edges of the field
+    ShrinkingArena,
+}
+
+impl GameMode {
+    /// Modes that can be chosen in the settings, the daily challenge has its own menu entry
+    pub const SELECTABLE: [GameMode; 5] = [
+        GameMode::Classic,
+        GameMode::TimeAttack,
+        GameMode::AppleRush,
+        GameMode::Survival,
+        GameMode::ShrinkingArena,
+    ];
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Daily => "Daily Challenge",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::AppleRush => "Apple Rush",
+            GameMode::Survival => "Survival",
+            GameMode::ShrinkingArena => "Shrinking Arena",
+        }
+    }
+
+    /// Returns the seconds after which a round of the mode ends
+    pub fn get_time_limit(&self) -> Option<u64> {
+        match self {
+            GameMode::TimeAttack => Some(TIME_ATTACK_SECONDS),
+            _ => None,
+        }
+    }
+
+    /// Returns the amount of apples that ends a round of the mode
+    pub fn get_apple_goal(&self) -> Option<u32> {
+        match self {
+            GameMode::AppleRush => Some(APPLE_RUSH_GOAL),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the score is made of points from the score events of the round
+    pub fn is_scored_by_points(&self) -> bool {
+        matches!(
+            self,
+            GameMode::Classic | GameMode::Daily | GameMode::Survival | GameMode::ShrinkingArena
+        )
+    }
+
+    /// Returns whether a lower score ranks higher, like a faster time
+    pub fn is_lower_score_better(&self) -> bool {
+        matches!(self, GameMode::AppleRush)
+    }
+
+    /// Returns the score as it is shown on the leaderboard,
+    /// times are kept in game ticks and shown with hundredths of a second
+    pub fn format_score(&self, score: i32) -> String {
+        if self.is_lower_score_better() {
+            convert_ticks_to_string(&(score.max(0) as u64))
+        } else {
+            score.to_string()
+        }
+    }
+}
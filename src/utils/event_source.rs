@@ -0,0 +1,254 @@
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crossterm::event;
+
+use crate::utils::enums::Event;
+
+/// Time between two game ticks
+pub const TICK_RATE: Duration = Duration::from_millis(50);
+/// Ticks that are caught up at once when the game falls behind, older ones are dropped
+pub const MAX_PENDING_TICKS: u32 = 3;
+/// Events that can wait in the queue before the input thread waits for the game
+const EVENT_CAPACITY: usize = 64;
+/// How long the threads wait before they look at the shutdown signal again
+const POLL_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Reads the keyboard and keeps time on background threads
+///
+/// Ticks that pile up while the game is busy share a single wake up in the queue, and at
+/// most [`MAX_PENDING_TICKS`] of them are caught up. The threads stop when the source is
+/// dropped.
+pub struct EventSource {
+    event_rx: Receiver<Event>,
+    /// Ticks that passed since the game last took them
+    pending_ticks: Arc<AtomicU32>,
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<io::Result<()>>>,
+}
+
+impl EventSource {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = mpsc::sync_channel(EVENT_CAPACITY);
+        let pending_ticks = Arc::new(AtomicU32::new(0));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let threads = vec![
+            spawn_input_thread(event_tx.clone(), Arc::clone(&shutdown)),
+            spawn_tick_thread(event_tx, Arc::clone(&pending_ticks), Arc::clone(&shutdown)),
+        ];
+        EventSource {
+            event_rx,
+            pending_ticks,
+            shutdown,
+            threads,
+        }
+    }
+
+    /// Waits until something happened and returns every event that is queued,
+    /// in the order they happened
+    ///
+    /// Each tick that passed is returned as its own [`Event::GameTick`]. Returns an error
+    /// if reading the terminal failed or a thread stopped.
+    pub fn next_events(&mut self) -> io::Result<Vec<Event>> {
+        let mut events = vec![];
+        match self.event_rx.recv_timeout(TICK_RATE) {
+            Ok(event) => self.push_event(&mut events, event),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Err(self.get_thread_error()),
+        }
+        while let Ok(event) = self.event_rx.try_recv() {
+            self.push_event(&mut events, event);
+        }
+        // Ticks whose wake up did not fit into the full queue
+        self.push_event(&mut events, Event::GameTick);
+        // The threads only stop on their own if something went wrong
+        if self.threads.iter().any(|thread| thread.is_finished()) {
+            return Err(self.get_thread_error());
+        }
+        Ok(events)
+    }
+
+    fn push_event(&self, events: &mut Vec<Event>, event: Event) {
+        match event {
+            Event::GameTick => {
+                let ticks = self.pending_ticks.swap(0, Ordering::AcqRel);
+                events.extend((0..ticks).map(|_| Event::GameTick));
+            }
+            event => events.push(event),
+        }
+    }
+
+    /// Returns why one of the threads stopped
+    fn get_thread_error(&mut self) -> io::Error {
+        let Some(index) = self.threads.iter().position(|thread| thread.is_finished()) else {
+            return io::Error::other("the event threads stopped");
+        };
+        match self.threads.swap_remove(index).join() {
+            Ok(Err(err)) => err,
+            Ok(Ok(())) => io::Error::other("an event thread stopped unexpectedly"),
+            Err(_) => io::Error::other("an event thread panicked"),
+        }
+    }
+}
+
+impl Default for EventSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Frees the input thread if it waits for room in a full queue
+        while self.event_rx.try_recv().is_ok() {}
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn spawn_input_thread(
+    event_tx: SyncSender<Event>,
+    shutdown: Arc<AtomicBool>,
+) -> JoinHandle<io::Result<()>> {
+    thread::spawn(move || {
+        while !shutdown.load(Ordering::Relaxed) {
+            // Waiting with a timeout instead of blocking in `read` lets the thread see the
+            // shutdown signal
+            if !event::poll(POLL_TIMEOUT)? {
+                continue;
+            }
+            if let event::Event::Key(key_event) = event::read()?
+                && event_tx.send(Event::Input(key_event)).is_err()
+            {
+                break;
+            }
+        }
+        Ok(())
+    })
+}
+
+fn spawn_tick_thread(
+    event_tx: SyncSender<Event>,
+    pending_ticks: Arc<AtomicU32>,
+    shutdown: Arc<AtomicBool>,
+) -> JoinHandle<io::Result<()>> {
+    thread::spawn(move || {
+        // Sleeping until a deadline keeps the pace steady even if a wake up comes late
+        let mut next_tick = Instant::now() + TICK_RATE;
+        while !shutdown.load(Ordering::Relaxed) {
+            thread::sleep(next_tick.saturating_duration_since(Instant::now()));
+            next_tick += TICK_RATE;
+            // Only the first tick of a batch wakes the game up, it takes the others with it
+            if add_pending_tick(&pending_ticks) {
+                match event_tx.try_send(Event::GameTick) {
+                    Ok(()) | Err(TrySendError::Full(_)) => {}
+                    Err(TrySendError::Disconnected(_)) => break,
+                }
+            }
+            // After a long stall the pace starts over instead of rushing to catch up
+            if next_tick < Instant::now() {
+                next_tick = Instant::now() + TICK_RATE;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Counts another tick that passed, up to [`MAX_PENDING_TICKS`]
+///
+/// Returns true if it is the first one since the game last took them.
+fn add_pending_tick(pending_ticks: &AtomicU32) -> bool {
+    let previous = pending_ticks
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |ticks| {
+            Some((ticks + 1).min(MAX_PENDING_TICKS))
+        })
+        .unwrap_or_default();
+    previous == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a source without the input thread, tests have no terminal to read
+    fn create_tick_source() -> EventSource {
+        let (event_tx, event_rx) = mpsc::sync_channel(EVENT_CAPACITY);
+        let pending_ticks = Arc::new(AtomicU32::new(0));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let threads = vec![spawn_tick_thread(
+            event_tx,
+            Arc::clone(&pending_ticks),
+            Arc::clone(&shutdown),
+        )];
+        EventSource {
+            event_rx,
+            pending_ticks,
+            shutdown,
+            threads,
+        }
+    }
+
+    fn count_ticks(events: &[Event]) -> usize {
+        events
+            .iter()
+            .filter(|event| matches!(event, Event::GameTick))
+            .count()
+    }
+
+    #[test]
+    fn pending_ticks_are_capped() {
+        let pending_ticks = AtomicU32::new(0);
+        assert!(add_pending_tick(&pending_ticks));
+        for _ in 0..10 {
+            assert!(!add_pending_tick(&pending_ticks));
+        }
+        assert_eq!(pending_ticks.load(Ordering::Acquire), MAX_PENDING_TICKS);
+    }
+
+    #[test]
+    fn queued_wake_ups_share_the_pending_ticks() {
+        let (event_tx, event_rx) = mpsc::sync_channel(EVENT_CAPACITY);
+        let mut source = EventSource {
+            event_rx,
+            pending_ticks: Arc::new(AtomicU32::new(MAX_PENDING_TICKS)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            threads: vec![],
+        };
+        event_tx.send(Event::GameTick).unwrap();
+        event_tx.send(Event::GameTick).unwrap();
+
+        let events = source.next_events().unwrap();
+        assert_eq!(count_ticks(&events), MAX_PENDING_TICKS as usize);
+        assert_eq!(count_ticks(&source.next_events().unwrap()), 0);
+    }
+
+    #[test]
+    fn a_busy_game_catches_up_on_few_ticks() {
+        let mut source = create_tick_source();
+        thread::sleep(TICK_RATE * 10);
+        let events = source.next_events().unwrap();
+        // A tick may pass while the events are collected
+        let ticks = MAX_PENDING_TICKS as usize;
+        assert!((ticks..=ticks + 1).contains(&count_ticks(&events)));
+    }
+
+    #[test]
+    fn dropping_the_source_stops_its_threads() {
+        let source = create_tick_source();
+        let pending_ticks = Arc::clone(&source.pending_ticks);
+        thread::sleep(TICK_RATE * 2);
+        drop(source);
+        // The tick thread held the other reference until it was joined
+        assert_eq!(Arc::strong_count(&pending_ticks), 1);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::utils::{
+    collectables::CollectableId,
+    enums::{DeathCause, Turn},
+};
+
+/// Something that happened during a round, passed to [`App::emit`](crate::game::App::emit)
+/// so stats and achievements can react to it
+pub enum GameEvent {
+    Collected(CollectableId),
+    Died(DeathCause),
+    /// A second of the round has passed
+    Second,
+    /// The snake turned left or right relative to where it was heading
+    Turned(Turn),
+    /// The round reached the given level
+    LevelUp(u32),
+    /// The effect of the item with the id ran out of time
+    EffectExpired(CollectableId),
+}
@@ -0,0 +1,65 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{arena::is_inside, enums::Direction};
+
+/// Hazards on the field when a Survival round starts
+pub const START_HAZARDS: usize = 3;
+/// Hazards never get more than this, one is added every level
+pub const MAX_HAZARDS: usize = 8;
+
+/// A block that wanders over the field in Survival mode, touching it kills the snake
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Hazard {
+    pub position: (f64, f64),
+    pub direction: Direction,
+    /// Game updates since the hazard last moved
+    move_counter: u32,
+}
+
+impl Hazard {
+    pub fn new(position: (f64, f64), direction: Direction) -> Self {
+        Hazard {
+            position,
+            direction,
+            move_counter: 0,
+        }
+    }
+
+    /// Called every game update, moves the hazard once enough updates passed
+    ///
+    /// Now and then the hazard turns, and it bounces back from the walls.
+    pub fn on_game_update(
+        &mut self,
+        move_interval: u32,
+        field_size: (u32, u32),
+        rng: &mut impl Rng,
+    ) {
+        self.move_counter += 1;
+        if self.move_counter < move_interval {
+            return;
+        }
+        self.move_counter = 0;
+        if rng.random_ratio(1, 5) {
+            self.direction = Direction::ALL[rng.random_range(0..Direction::ALL.len())];
+        }
+        let mut next = get_next_position(self.position, self.direction);
+        if !is_inside(next, 0, field_size) {
+            self.direction = self.direction.get_opposite();
+            next = get_next_position(self.position, self.direction);
+        }
+        if is_inside(next, 0, field_size) {
+            self.position = next;
+        }
+    }
+}
+
+/// Returns after how many game updates hazards move, they get faster the longer a round lasts
+pub fn get_hazard_move_interval(round_time: u64) -> u32 {
+    4u32.saturating_sub((round_time / 45) as u32).max(1)
+}
+
+fn get_next_position(position: (f64, f64), direction: Direction) -> (f64, f64) {
+    let (dx, dy) = direction.get_vector();
+    (position.0 + dx, position.1 + dy)
+}
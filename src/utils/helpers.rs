@@ -1,4 +1,12 @@
-use crate::utils::enums::Direction;
+use std::env;
+use std::fs::{File, create_dir_all};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::utils::{animations::TICKS_PER_SECOND, enums::Direction};
 
 /// Converts seconds to a string in the format "MM:SS"
 pub fn convert_seconds_to_string(seconds: &u64) -> String {
@@ -7,7 +15,18 @@ pub fn convert_seconds_to_string(seconds: &u64) -> String {
     format!("{:02}:{:02}", minutes, remaining_seconds)
 }
 
-pub fn get_directionvector_from_snake(snake: &Vec<(f64, f64)>) -> (f64, f64) {
+/// Converts game ticks to a string in the format "MM:SS.ss"
+pub fn convert_ticks_to_string(ticks: &u64) -> String {
+    let ticks_per_second = TICKS_PER_SECOND as u64;
+    let hundredths = ticks % ticks_per_second * 100 / ticks_per_second;
+    format!(
+        "{}.{:02}",
+        convert_seconds_to_string(&(ticks / ticks_per_second)),
+        hundredths
+    )
+}
+
+pub fn get_directionvector_from_snake(snake: &[(f64, f64)]) -> (f64, f64) {
     (snake[0].0 - snake[1].0, (snake[0].1 - snake[1].1))
 }
 
@@ -26,3 +45,86 @@ pub fn get_direction_from_vector(vector: &(f64, f64)) -> Direction {
         }
     }
 }
+
+/// Returns the days since the unix epoch
+pub fn get_current_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() / 86_400)
+}
+
+/// Returns today's date in UTC in the format "YYYY-MM-DD"
+pub fn get_current_date() -> String {
+    // Converts days to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let days = get_current_day() as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Returns the entry after or before the current one, wrapping around at both ends
+pub fn get_cycled<T: Copy + PartialEq>(entries: &[T], current: T, forward: bool) -> T {
+    let index = entries
+        .iter()
+        .position(|entry| *entry == current)
+        .unwrap_or(0);
+    let next = if forward {
+        (index + 1) % entries.len()
+    } else {
+        (index + entries.len() - 1) % entries.len()
+    };
+    entries[next]
+}
+
+/// Returns the path of a file in the data directory of the game,
+/// creating the directory if it does not exist yet
+pub fn get_data_file_path(file_name: &str) -> io::Result<String> {
+    let variable = if cfg!(target_os = "windows") {
+        "APPDATA"
+    } else {
+        "HOME"
+    };
+    let mut path_buf = env::var(variable).map(PathBuf::from).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("the {} environment variable is not set", variable),
+        )
+    })?;
+
+    if cfg!(target_os = "linux") || cfg!(target_os = "macos") {
+        path_buf.push(".local/share");
+    }
+
+    path_buf.push("snake-ratatui");
+
+    if !path_buf.exists() {
+        create_dir_all(&path_buf).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("could not create {}: {}", path_buf.display(), err),
+            )
+        })?;
+    }
+
+    path_buf.push(file_name);
+
+    Ok(path_buf.to_string_lossy().to_string())
+}
+
+/// Writes the data as JSON into a file in the data directory, replacing its old content
+pub fn save_data_file(file_name: &str, data: &impl Serialize) -> io::Result<()> {
+    let json_data = serde_json::to_string(data).map_err(io::Error::other)?;
+    let path = get_data_file_path(file_name)?;
+    File::create(&path)?.write_all(json_data.as_bytes())
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    enums::{DeathCause, GameMode},
+    helpers::{get_current_day, get_data_file_path, save_data_file},
+    stats::RunStats,
+};
+
+/// Amount of recent scores that are kept for the score chart
+const RECENT_SCORES: usize = 50;
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModeStats {
+    pub games_played: u32,
+    pub total_score: i64,
+    pub best_score: i32,
+}
+
+impl ModeStats {
+    pub fn get_average_score(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+        self.total_score as f64 / self.games_played as f64
+    }
+}
+
+/// Everything a player has done over all of their rounds
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub games_played: u32,
+    /// Seconds spent in rounds
+    pub play_time: u64,
+    pub apples_eaten: u32,
+    pub deaths: HashMap<DeathCause, u32>,
+    pub modes: HashMap<GameMode, ModeStats>,
+    /// The latest scores, oldest first
+    pub recent_scores: Vec<i32>,
+    /// Days since the unix epoch of the last round
+    pub last_played_day: Option<u64>,
+    /// Days in a row with at least one round
+    pub daily_streak: u32,
+    pub best_daily_streak: u32,
+}
+
+impl LifetimeStats {
+    fn add_run(
+        &mut self,
+        run: &RunStats,
+        game_mode: GameMode,
+        score: Option<i32>,
+        round_time: u64,
+    ) {
+        self.games_played += 1;
+        self.play_time += round_time;
+        self.apples_eaten += run.apples_eaten;
+        if let Some(cause) = run.death_cause {
+            *self.deaths.entry(cause).or_default() += 1;
+        }
+
+        if let Some(score) = score {
+            let mode = self.modes.entry(game_mode).or_default();
+            mode.best_score = if mode.games_played == 0 {
+                score
+            } else if game_mode.is_lower_score_better() {
+                mode.best_score.min(score)
+            } else {
+                mode.best_score.max(score)
+            };
+            mode.games_played += 1;
+            mode.total_score += score as i64;
+
+            self.recent_scores.push(score);
+            if self.recent_scores.len() > RECENT_SCORES {
+                self.recent_scores.remove(0);
+            }
+        }
+
+        let today = get_current_day();
+        self.daily_streak = match self.last_played_day {
+            Some(day) if day == today => self.daily_streak,
+            Some(day) if day + 1 == today => self.daily_streak + 1,
+            _ => 1,
+        };
+        self.best_daily_streak = self.best_daily_streak.max(self.daily_streak);
+        self.last_played_day = Some(today);
+    }
+
+    /// Returns the streak, or 0 if it was broken because yesterday had no round
+    pub fn get_current_streak(&self) -> u32 {
+        match self.last_played_day {
+            Some(day) if day + 1 >= get_current_day() => self.daily_streak,
+            _ => 0,
+        }
+    }
+}
+
+/// Lifetime stats of every player, saved in `stats.json`
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsStore {
+    players: HashMap<String, LifetimeStats>,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+impl StatsStore {
+    pub fn load() -> Self {
+        let mut store: StatsStore = match get_data_file_path("stats.json").and_then(read_to_string)
+        {
+            Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or_default(),
+            Err(_err) => StatsStore::default(),
+        };
+        store.persistent = true;
+        store
+    }
+
+    /// Adds a finished round to the stats of the player and saves them,
+    /// the score is `None` if the round ended before it had one, like an unfinished Apple Rush
+    ///
+    /// The round is counted even if the stats could not be saved.
+    pub fn record_run(
+        &mut self,
+        player_name: &str,
+        run: &RunStats,
+        game_mode: GameMode,
+        score: Option<i32>,
+        round_time: u64,
+    ) -> io::Result<()> {
+        self.players
+            .entry(player_name.to_string())
+            .or_default()
+            .add_run(run, game_mode, score, round_time);
+        self.save()
+    }
+
+    pub fn get(&self, player_name: &str) -> Option<&LifetimeStats> {
+        self.players.get(player_name)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if !self.persistent {
+            return Ok(());
+        }
+        save_data_file("stats.json", self)
+    }
+}
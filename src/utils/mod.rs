@@ -1,4 +1,22 @@
+pub mod achievements;
+pub mod animations;
+pub mod arena;
 pub mod collectables;
+pub mod config;
+pub mod effects;
 pub mod enums;
+pub mod event_source;
+pub mod game_events;
+pub mod hazards;
 pub mod helpers;
+pub mod lifetime_stats;
+pub mod registry;
+pub mod replay;
+pub mod save_game;
 pub mod scores;
+pub mod scoring;
+pub mod settings;
+pub mod sound;
+pub mod spawn_table;
+pub mod stats;
+pub mod theme;
@@ -0,0 +1,224 @@
+use ratatui::style::Color;
+
+use crate::utils::{
+    collectables::{
+        APPLE, AppleCollectable, BOMB, BombCollectable, Collectable, CollectableId, GHOST,
+        GOLDEN_APPLE, GhostCollectable, GoldenAppleCollectable, MAGNET, MagnetCollectable, PORTAL,
+        PortalCollectable, REVERSE, ReverseCollectable, SHIELD, SHRINK, SLOW, SPEED,
+        ShieldCollectable, ShrinkCollectable, SlowCollectable, SpeedCollectable,
+    },
+    effects::{EffectDefinition, StackRule},
+    spawn_table::SpawnRule,
+};
+
+type CollectableFactory = Box<dyn Fn(f64, f64) -> Box<dyn Collectable>>;
+
+/// What an item is for, which decides how scoring, game modes and item mixes treat it
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ItemKind {
+    /// Counts toward the apples eaten and keeps the combo going
+    Apple,
+    #[default]
+    PowerUp,
+    /// Hurts the snake, item mixes with more hazards make it more likely
+    Hazard,
+}
+
+/// Everything the game needs to know about a kind of collectable
+pub struct CollectableDefinition {
+    pub id: CollectableId,
+    pub name: &'static str,
+    pub glyph: char,
+    pub color: Color,
+    /// Points for collecting the item before any bonus
+    pub points: i32,
+    pub kind: ItemKind,
+    /// Items without a spawn rule only appear when spawned by code, like the apple
+    pub spawn_rule: Option<SpawnRule>,
+    /// Effect the item grants, it has the id, name and color of the item
+    pub effect: Option<EffectDefinition>,
+    factory: CollectableFactory,
+}
+
+impl CollectableDefinition {
+    pub fn new<T: Collectable + 'static>(
+        id: CollectableId,
+        name: &'static str,
+        glyph: char,
+        color: Color,
+    ) -> Self {
+        CollectableDefinition {
+            id,
+            name,
+            glyph,
+            color,
+            points: 0,
+            kind: ItemKind::default(),
+            spawn_rule: None,
+            effect: None,
+            factory: Box::new(|x, y| Box::new(T::new(x, y))),
+        }
+    }
+
+    pub fn points(mut self, points: i32) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn kind(mut self, kind: ItemKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn spawn_rule(mut self, spawn_rule: SpawnRule) -> Self {
+        self.spawn_rule = Some(spawn_rule);
+        self
+    }
+
+    pub fn effect(mut self, effect: EffectDefinition) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Returns whether the item counts as an apple
+    pub fn is_apple(&self) -> bool {
+        self.kind == ItemKind::Apple
+    }
+
+    pub fn create(&self, x: f64, y: f64) -> Box<dyn Collectable> {
+        (self.factory)(x, y)
+    }
+}
+
+pub struct CollectableRegistry {
+    definitions: Vec<CollectableDefinition>,
+}
+
+impl CollectableRegistry {
+    pub fn new() -> Self {
+        CollectableRegistry {
+            definitions: vec![],
+        }
+    }
+
+    /// Adds a new kind of collectable, replacing any definition with the same id
+    pub fn register(&mut self, definition: CollectableDefinition) {
+        self.definitions
+            .retain(|registered| registered.id != definition.id);
+        self.definitions.push(definition);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CollectableDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.id == id)
+    }
+
+    pub fn get_definitions(&self) -> impl Iterator<Item = &CollectableDefinition> {
+        self.definitions.iter()
+    }
+}
+
+impl Default for CollectableRegistry {
+    /// Creates a registry containing all built-in collectables
+    fn default() -> Self {
+        let mut registry = CollectableRegistry::new();
+        registry.register(
+            CollectableDefinition::new::<AppleCollectable>(APPLE, "Apple", '●', Color::Red)
+                .points(1)
+                .kind(ItemKind::Apple),
+        );
+        registry.register(
+            CollectableDefinition::new::<SpeedCollectable>(SPEED, "Speed", '»', Color::Yellow)
+                .spawn_rule(SpawnRule::new(10).max_on_board(2))
+                .effect(
+                    EffectDefinition::new(StackRule::Stack(2))
+                        .on_apply(|app| app.game_speed += 1)
+                        .on_expire(|app| app.game_speed -= 1),
+                ),
+        );
+        registry.register(
+            CollectableDefinition::new::<SlowCollectable>(SLOW, "Slow", '«', Color::Cyan)
+                .spawn_rule(SpawnRule::new(8))
+                .effect(
+                    EffectDefinition::new(StackRule::Extend)
+                        .on_apply(|app| app.game_speed -= 1)
+                        .on_expire(|app| app.game_speed += 1),
+                ),
+        );
+        registry.register(
+            CollectableDefinition::new::<ReverseCollectable>(REVERSE, "Reverse", '⇄', Color::Blue)
+                .spawn_rule(SpawnRule::new(8).min_snake_length(8)),
+        );
+        registry.register(
+            CollectableDefinition::new::<GoldenAppleCollectable>(
+                GOLDEN_APPLE,
+                "Golden Apple",
+                '★',
+                Color::LightYellow,
+            )
+            .points(3)
+            .kind(ItemKind::Apple)
+            .spawn_rule(SpawnRule::new(5).min_round_time(30).despawn_after(Some(8))),
+        );
+        registry.register(
+            CollectableDefinition::new::<ShrinkCollectable>(
+                SHRINK,
+                "Shrink",
+                '✂',
+                Color::LightCyan,
+            )
+            .spawn_rule(
+                SpawnRule::new(4)
+                    .min_snake_length(10)
+                    .despawn_after(Some(15)),
+            ),
+        );
+        registry.register(
+            CollectableDefinition::new::<GhostCollectable>(GHOST, "Ghost", '☁', Color::White)
+                .spawn_rule(
+                    SpawnRule::new(4)
+                        .min_snake_length(8)
+                        .min_round_time(30)
+                        .despawn_after(Some(15)),
+                )
+                .effect(EffectDefinition::new(StackRule::Refresh)),
+        );
+        registry.register(
+            CollectableDefinition::new::<MagnetCollectable>(
+                MAGNET,
+                "Magnet",
+                '∩',
+                Color::LightMagenta,
+            )
+            .spawn_rule(SpawnRule::new(4).min_round_time(30).despawn_after(Some(15)))
+            .effect(EffectDefinition::new(StackRule::Refresh)),
+        );
+        registry.register(
+            CollectableDefinition::new::<BombCollectable>(BOMB, "Bomb", '✹', Color::DarkGray)
+                .kind(ItemKind::Hazard)
+                .spawn_rule(
+                    SpawnRule::new(4)
+                        .weight_per_level(2)
+                        .min_round_time(20)
+                        .max_on_board(2)
+                        .despawn_after(Some(30)),
+                ),
+        );
+        registry.register(
+            CollectableDefinition::new::<PortalCollectable>(PORTAL, "Portal", '◎', Color::Magenta)
+                .spawn_rule(SpawnRule::new(3).min_round_time(60)),
+        );
+        registry.register(
+            CollectableDefinition::new::<ShieldCollectable>(
+                SHIELD,
+                "Shield",
+                '⛨',
+                Color::LightBlue,
+            )
+            .spawn_rule(SpawnRule::new(3).min_snake_length(12).min_round_time(60))
+            .effect(EffectDefinition::new(StackRule::Stack(1))),
+        );
+        registry
+    }
+}
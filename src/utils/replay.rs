@@ -0,0 +1,41 @@
+use std::fs::{File, read_to_string};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{config::GameConfig, enums::Direction, helpers::get_data_file_path};
+
+/// A change of direction and the round tick it happened at
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ReplayInput {
+    pub tick: u64,
+    pub direction: Direction,
+}
+
+/// A recorded round, which plays out the same way again because its seed is stored as well
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub config: GameConfig,
+    pub inputs: Vec<ReplayInput>,
+}
+
+impl Replay {
+    /// Writes the replay to a new file in the data directory and returns its path
+    pub fn save(&self) -> io::Result<String> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let path = get_data_file_path(&format!("replay-{}.json", timestamp))?;
+        let json_data = serde_json::to_string(self).map_err(io::Error::other)?;
+        File::create(&path)?.write_all(json_data.as_bytes())?;
+        Ok(path)
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        let raw_string = read_to_string(path)?;
+        let replay: Replay = serde_json::from_str(&raw_string).map_err(io::Error::other)?;
+        replay.config.validate()?;
+        Ok(replay)
+    }
+}
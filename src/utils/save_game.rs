@@ -0,0 +1,90 @@
+use std::fs::{self, read_to_string};
+use std::io;
+
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    arena::Arena,
+    config::GameConfig,
+    enums::Direction,
+    hazards::Hazard,
+    helpers::{get_data_file_path, save_data_file},
+    replay::ReplayInput,
+    scoring::Scoring,
+    stats::RunStats,
+};
+
+const SAVE_FILE: &str = "savegame.json";
+
+/// An item on the field of a saved round
+#[derive(Serialize, Deserialize)]
+pub struct SavedCollectable {
+    /// Id of the item in the registry
+    pub collectable_type: String,
+    pub positions: Vec<(f64, f64)>,
+    pub despawn_timer: Option<u32>,
+}
+
+/// An active effect of a saved round
+#[derive(Serialize, Deserialize)]
+pub struct SavedEffect {
+    /// Id of the item that granted the effect
+    pub effect_type: String,
+    pub duration: u32,
+    pub remaining_time: u32,
+}
+
+/// A round that was left before it ended, saved on quit so it can be continued later
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub config: GameConfig,
+    pub snake: Vec<(f64, f64)>,
+    pub direction: Direction,
+    pub collectables: Vec<SavedCollectable>,
+    pub effects: Vec<SavedEffect>,
+    pub hazards: Vec<Hazard>,
+    pub arena: Option<Arena>,
+    pub game_speed: i32,
+    pub move_counter: u32,
+    pub round_time: u64,
+    pub round_ticks: u64,
+    pub random_item_timer: u32,
+    pub scoring: Scoring,
+    pub stats: RunStats,
+    /// Inputs of the round so far, so the replay of the continued round is complete
+    pub recorded_inputs: Vec<ReplayInput>,
+    /// State of the random number generator, so the round goes on the same way
+    pub rng: ChaCha12Rng,
+    /// The round is a daily challenge that is not ranked
+    #[serde(default)]
+    pub practice_round: bool,
+}
+
+impl SaveGame {
+    /// Loads the saved round, if there is one that can be continued
+    pub fn load() -> Option<Self> {
+        let raw_string = get_data_file_path(SAVE_FILE)
+            .and_then(read_to_string)
+            .ok()?;
+        let save: SaveGame = serde_json::from_str(&raw_string).ok()?;
+        save.is_valid().then_some(save)
+    }
+
+    /// Returns whether the round can be continued, a changed save file could break the game
+    pub fn is_valid(&self) -> bool {
+        // Items like the reverse item look at the cell behind the head
+        self.config.validate().is_ok() && self.snake.len() >= 2
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_data_file(SAVE_FILE, self)
+    }
+
+    /// Removes the saved round, so it can only be continued once
+    pub fn delete() {
+        if let Ok(path) = get_data_file_path(SAVE_FILE) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
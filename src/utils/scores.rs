@@ -1,84 +1,282 @@
-use std::env;
-use std::fs::{File, create_dir_all, read_to_string};
-use std::io::Write;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::io;
 
 use serde::{Deserialize, Serialize};
 
+use crate::utils::{
+    enums::GameMode,
+    helpers::{get_data_file_path, save_data_file},
+};
+
 #[derive(Serialize, Deserialize)]
 pub struct Score {
     pub player_name: String,
     pub score: i32,
+    /// Leaderboard the score belongs to, scores saved before there were modes are classic
+    #[serde(default)]
+    pub game_mode: GameMode,
+    /// The round was saved on quit and continued later
+    #[serde(default)]
+    pub resumed: bool,
 }
 
 pub struct ScoreManager {
-    scores: Vec<Score>,
+    /// Leaderboards of the game modes, best score first
+    scores: HashMap<GameMode, Vec<Score>>,
+    /// Scores of the daily challenges by their date
+    daily_scores: HashMap<String, Vec<Score>>,
+    /// Players that started the daily challenge of a date, an attempt counts from its start
+    /// even if it never ends with a score
+    daily_attempts: HashMap<String, Vec<String>>,
+    persistent: bool,
+}
+
+impl Default for ScoreManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScoreManager {
     pub fn new() -> Self {
-        let mut manager = ScoreManager { scores: vec![] };
+        let mut manager = ScoreManager {
+            scores: HashMap::new(),
+            daily_scores: HashMap::new(),
+            daily_attempts: HashMap::new(),
+            persistent: true,
+        };
         manager.load_scores();
         manager.sort_scores();
         manager
     }
 
-    pub fn add_score(&mut self, name: String, score: i32) {
-        let new_score = Score {
-            player_name: name,
-            score,
-        };
-        self.scores.push(new_score);
+    /// Creates a manager that neither loads nor saves scores
+    pub fn in_memory() -> Self {
+        ScoreManager {
+            scores: HashMap::new(),
+            daily_scores: HashMap::new(),
+            daily_attempts: HashMap::new(),
+            persistent: false,
+        }
+    }
+
+    /// Adds a score to the leaderboard of its mode and returns its position there, starting at 1
+    ///
+    /// The score stays on the leaderboard even if the leaderboard could not be saved.
+    pub fn add_score(&mut self, score: Score) -> io::Result<usize> {
+        let scores = self.scores.entry(score.game_mode).or_default();
+        let rank = get_rank(scores, score.game_mode, score.score);
+        scores.push(score);
         self.sort_scores();
-        self.save_scores();
+        self.save_scores()?;
+        Ok(rank)
     }
 
-    fn save_scores(&mut self) {
-        let json_data = serde_json::to_string(&self.scores).expect("Failed to serialize");
-        let path = Self::get_save_file_path();
-        let mut file = File::create(&path).expect(&format!("Failed to create file at {}", path));
-        file.write_all(json_data.as_bytes())
-            .expect("Failed to write JSON to file");
+    /// Returns the best score of the player in the mode, if they have any
+    pub fn get_best_score(&self, game_mode: GameMode, name: &str) -> Option<i32> {
+        self.get_scores(game_mode)
+            .iter()
+            .find(|score| score.player_name == name)
+            .map(|score| score.score)
+    }
+
+    /// Adds the score of a daily challenge and returns its position on the
+    /// leaderboard of that day, starting at 1
+    pub fn add_daily_score(&mut self, date: &str, score: Score) -> io::Result<usize> {
+        let scores = self.daily_scores.entry(date.to_string()).or_default();
+        let rank = get_rank(scores, GameMode::Daily, score.score);
+        scores.push(score);
+        scores.sort_by_key(|score| std::cmp::Reverse(score.score));
+        self.save_daily_scores()?;
+        Ok(rank)
+    }
+
+    /// Returns the scores of the daily challenge of the date, highest first
+    pub fn get_daily_scores(&self, date: &str) -> &[Score] {
+        self.daily_scores.get(date).map_or(&[], Vec::as_slice)
+    }
+
+    /// Marks the daily challenge of the date as attempted by the player
+    pub fn add_daily_attempt(&mut self, date: &str, name: &str) -> io::Result<()> {
+        let players = self.daily_attempts.entry(date.to_string()).or_default();
+        if !players.iter().any(|player| player == name) {
+            players.push(name.to_string());
+        }
+        if !self.persistent {
+            return Ok(());
+        }
+        save_data_file("daily_attempts.json", &self.daily_attempts)
+    }
+
+    /// Returns whether the player already used their attempt at the daily challenge of the date
+    pub fn has_daily_attempt(&self, date: &str, name: &str) -> bool {
+        // Scores from before attempts were kept count as attempts as well
+        self.daily_attempts
+            .get(date)
+            .is_some_and(|players| players.iter().any(|player| player == name))
+            || self
+                .get_daily_scores(date)
+                .iter()
+                .any(|score| score.player_name == name)
+    }
+
+    fn save_scores(&mut self) -> io::Result<()> {
+        if !self.persistent {
+            return Ok(());
+        }
+        // Saved as one list, the mode of every score tells its leaderboard
+        let scores: Vec<&Score> = self.scores.values().flatten().collect();
+        save_data_file("scores.json", &scores)
+    }
+
+    fn save_daily_scores(&mut self) -> io::Result<()> {
+        if !self.persistent {
+            return Ok(());
+        }
+        save_data_file("daily_scores.json", &self.daily_scores)
     }
 
     fn load_scores(&mut self) {
-        let path = Self::get_save_file_path();
-        let result = match read_to_string(&path) {
+        let result: Vec<Score> = match get_data_file_path("scores.json").and_then(read_to_string) {
             Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or(Vec::new()),
             Err(_err) => Vec::new(),
         };
-        self.scores = result;
+        self.scores = HashMap::new();
+        for score in result {
+            self.scores.entry(score.game_mode).or_default().push(score);
+        }
+        let path = get_data_file_path("daily_scores.json");
+        self.daily_scores = match path.and_then(read_to_string) {
+            Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or_default(),
+            Err(_err) => HashMap::new(),
+        };
+        let path = get_data_file_path("daily_attempts.json");
+        self.daily_attempts = match path.and_then(read_to_string) {
+            Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or_default(),
+            Err(_err) => HashMap::new(),
+        };
     }
 
     fn sort_scores(&mut self) {
-        self.scores.sort_by(|a, b| b.score.cmp(&a.score));
+        for (game_mode, scores) in &mut self.scores {
+            if game_mode.is_lower_score_better() {
+                scores.sort_by_key(|score| score.score);
+            } else {
+                scores.sort_by_key(|score| std::cmp::Reverse(score.score));
+            }
+        }
     }
 
-    pub fn get_scores(&self) -> &Vec<Score> {
-        return &self.scores;
+    /// Returns the leaderboard of the mode, best score first
+    pub fn get_scores(&self, game_mode: GameMode) -> &[Score] {
+        self.scores.get(&game_mode).map_or(&[], Vec::as_slice)
     }
+}
 
-    fn get_save_file_path() -> String {
-        let mut path_buf = if cfg!(target_os = "windows") {
-            let appdata = env::var("APPDATA").expect("APPDATA environment variable not found");
-            PathBuf::from(appdata)
-        } else {
-            let home = env::var("HOME").expect("HOME environment variable not found");
-            PathBuf::from(home)
-        };
+/// Returns the position a new score would get on the sorted leaderboard
+fn get_rank(scores: &[Score], game_mode: GameMode, score: i32) -> usize {
+    scores
+        .iter()
+        .filter(|existing| {
+            if game_mode.is_lower_score_better() {
+                existing.score <= score
+            } else {
+                existing.score >= score
+            }
+        })
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if cfg!(target_os = "linux") || cfg!(target_os = "macos") {
-            path_buf.push(".local/share");
+    fn create_score(score: i32, game_mode: GameMode) -> Score {
+        Score {
+            player_name: String::from("you"),
+            score,
+            game_mode,
+            resumed: false,
         }
+    }
 
-        path_buf.push("snake-ratatui");
+    #[test]
+    fn rank_counts_the_scores_that_are_at_least_as_good() {
+        let scores = [10, 7, 7, 3].map(|score| create_score(score, GameMode::Classic));
+        assert_eq!(get_rank(&scores, GameMode::Classic, 12), 1);
+        assert_eq!(get_rank(&scores, GameMode::Classic, 7), 4);
+        assert_eq!(get_rank(&scores, GameMode::Classic, 0), 5);
 
-        if !path_buf.exists() {
-            create_dir_all(&path_buf).expect("Failed to create score directory");
-        }
+        let times = [30, 45].map(|score| create_score(score, GameMode::AppleRush));
+        assert_eq!(get_rank(&times, GameMode::AppleRush, 20), 1);
+        assert_eq!(get_rank(&times, GameMode::AppleRush, 40), 2);
+    }
+
+    #[test]
+    fn scores_are_ranked_on_the_leaderboard_of_their_mode() {
+        let mut manager = ScoreManager::in_memory();
+        assert_eq!(
+            manager
+                .add_score(create_score(5, GameMode::Classic))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            manager
+                .add_score(create_score(9, GameMode::Classic))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            manager
+                .add_score(create_score(50, GameMode::AppleRush))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            manager
+                .add_score(create_score(40, GameMode::AppleRush))
+                .unwrap(),
+            1
+        );
+
+        let classic: Vec<i32> = manager
+            .get_scores(GameMode::Classic)
+            .iter()
+            .map(|score| score.score)
+            .collect();
+        assert_eq!(classic, [9, 5]);
+        assert_eq!(manager.get_best_score(GameMode::AppleRush, "you"), Some(40));
+        assert!(manager.get_scores(GameMode::TimeAttack).is_empty());
+    }
 
-        path_buf.push("scores.json");
+    #[test]
+    fn daily_scores_are_kept_per_day() {
+        let mut manager = ScoreManager::in_memory();
+        let date = "2024-01-01";
+        assert!(!manager.has_daily_attempt(date, "you"));
+        assert_eq!(
+            manager
+                .add_daily_score(date, create_score(4, GameMode::Daily))
+                .unwrap(),
+            1
+        );
+        let mut other = create_score(8, GameMode::Daily);
+        other.player_name = String::from("someone");
+        assert_eq!(manager.add_daily_score(date, other).unwrap(), 1);
 
-        path_buf.to_str().unwrap().to_string()
+        assert!(manager.has_daily_attempt(date, "you"));
+        assert!(!manager.has_daily_attempt("2024-01-02", "you"));
+        manager.add_daily_attempt("2024-01-02", "you").unwrap();
+        assert!(manager.has_daily_attempt("2024-01-02", "you"));
+        assert!(manager.get_daily_scores("2024-01-02").is_empty());
+        let scores: Vec<i32> = manager
+            .get_daily_scores(date)
+            .iter()
+            .map(|score| score.score)
+            .collect();
+        assert_eq!(scores, [8, 4]);
     }
 }
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{collectables::SPEED, game_events::GameEvent, registry::CollectableRegistry};
+
+/// Game ticks in which the next apple has to be eaten to keep the combo going
+pub const COMBO_WINDOW_TICKS: u64 = 100;
+/// The combo multiplier does not grow beyond this
+pub const MAX_MULTIPLIER: u32 = 5;
+/// Seconds a round has to last for every time bonus point
+const TIME_BONUS_SECONDS: u64 = 10;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScoreReason {
+    /// Id of the collected item, kept as text so it can be saved with the round and looked up
+    /// in the registry again
+    Collected(String),
+    /// Given for every few seconds the snake stays alive
+    TimeBonus,
+}
+
+/// A single change of the score, with how it was calculated
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScoreEvent {
+    pub reason: ScoreReason,
+    /// Points of the item before any bonus
+    pub base_points: i32,
+    /// Combo multiplier the base points were multiplied with
+    pub multiplier: u32,
+    /// Points added for moving faster than normal
+    pub speed_bonus: i32,
+    pub points: i32,
+    /// Round tick the change happened in
+    pub tick: u64,
+}
+
+/// Score of a round, built up from the events of the round instead of the length of the snake
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Scoring {
+    pub total: i32,
+    /// Every change of the score in the order they happened
+    pub events: Vec<ScoreEvent>,
+    /// Apples eaten in a row, each within the combo window of the one before
+    combo: u32,
+    last_apple_tick: Option<u64>,
+    seconds: u64,
+}
+
+impl Scoring {
+    /// Updates the score and returns the change it caused, if any
+    pub fn on_event(
+        &mut self,
+        event: &GameEvent,
+        registry: &CollectableRegistry,
+        game_speed: i32,
+        tick: u64,
+    ) -> Option<&ScoreEvent> {
+        let score_event = match event {
+            GameEvent::Collected(collectable_type) => {
+                let definition = registry.get(collectable_type);
+                let base_points = definition.map_or(0, |definition| definition.points);
+                let is_apple = definition.is_some_and(|definition| definition.is_apple());
+                if is_apple {
+                    self.combo = if self.is_combo_running(tick) {
+                        self.combo + 1
+                    } else {
+                        1
+                    };
+                    self.last_apple_tick = Some(tick);
+                }
+                if base_points == 0 {
+                    return None;
+                }
+                let multiplier = if is_apple { self.get_multiplier() } else { 1 };
+                let speed_bonus = game_speed.max(0);
+                ScoreEvent {
+                    reason: ScoreReason::Collected(collectable_type.to_string()),
+                    base_points,
+                    multiplier,
+                    speed_bonus,
+                    points: base_points * multiplier as i32 + speed_bonus,
+                    tick,
+                }
+            }
+            GameEvent::Second => {
+                self.seconds += 1;
+                if !self.seconds.is_multiple_of(TIME_BONUS_SECONDS) {
+                    return None;
+                }
+                ScoreEvent {
+                    reason: ScoreReason::TimeBonus,
+                    base_points: 1,
+                    multiplier: 1,
+                    speed_bonus: 0,
+                    points: 1,
+                    tick,
+                }
+            }
+            // Without the extra speed the combo could not have been kept up
+            GameEvent::EffectExpired(SPEED) => {
+                self.end_combo();
+                return None;
+            }
+            _ => return None,
+        };
+        self.total += score_event.points;
+        self.events.push(score_event);
+        self.events.last()
+    }
+
+    /// Returns whether the next apple eaten at the tick would continue the combo
+    pub fn is_combo_running(&self, tick: u64) -> bool {
+        self.last_apple_tick
+            .is_some_and(|last| tick <= last + COMBO_WINDOW_TICKS)
+    }
+
+    /// Returns the apples of the running combo, 0 once its window passed
+    pub fn get_combo(&self, tick: u64) -> u32 {
+        if self.is_combo_running(tick) {
+            self.combo
+        } else {
+            0
+        }
+    }
+
+    /// Returns how much of the combo window is left at the tick, from 1 right after an apple to 0
+    pub fn get_combo_time_left(&self, tick: u64) -> f64 {
+        match self.last_apple_tick {
+            Some(last) if self.is_combo_running(tick) => {
+                1.0 - tick.saturating_sub(last) as f64 / COMBO_WINDOW_TICKS as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn end_combo(&mut self) {
+        self.combo = 0;
+        self.last_apple_tick = None;
+    }
+
+    /// Returns the multiplier for the current combo, 1 without a combo
+    pub fn get_multiplier(&self) -> u32 {
+        self.combo.clamp(1, MAX_MULTIPLIER)
+    }
+
+    /// Returns the points that came from combo multipliers
+    pub fn get_combo_bonus(&self) -> i32 {
+        self.events
+            .iter()
+            .map(|event| event.base_points * (event.multiplier as i32 - 1))
+            .sum()
+    }
+
+    pub fn get_speed_bonus(&self) -> i32 {
+        self.events.iter().map(|event| event.speed_bonus).sum()
+    }
+
+    pub fn get_time_bonus(&self) -> i32 {
+        self.events
+            .iter()
+            .filter(|event| event.reason == ScoreReason::TimeBonus)
+            .map(|event| event.points)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::collectables::{APPLE, GOLDEN_APPLE, SLOW};
+
+    fn eat_apple(scoring: &mut Scoring, registry: &CollectableRegistry, tick: u64) -> i32 {
+        scoring
+            .on_event(&GameEvent::Collected(APPLE), registry, 0, tick)
+            .map_or(0, |event| event.points)
+    }
+
+    #[test]
+    fn combo_runs_within_the_window() {
+        let registry = CollectableRegistry::default();
+        let mut scoring = Scoring::default();
+        eat_apple(&mut scoring, &registry, 0);
+        eat_apple(&mut scoring, &registry, COMBO_WINDOW_TICKS);
+        assert_eq!(scoring.get_combo(COMBO_WINDOW_TICKS), 2);
+        assert_eq!(scoring.get_multiplier(), 2);
+        assert_eq!(scoring.get_combo(2 * COMBO_WINDOW_TICKS + 1), 0);
+
+        eat_apple(&mut scoring, &registry, 2 * COMBO_WINDOW_TICKS + 1);
+        assert_eq!(scoring.get_multiplier(), 1);
+    }
+
+    #[test]
+    fn multiplier_is_capped() {
+        let registry = CollectableRegistry::default();
+        let mut scoring = Scoring::default();
+        let points: Vec<i32> = (0..8)
+            .map(|tick| eat_apple(&mut scoring, &registry, tick))
+            .collect();
+        assert_eq!(points, [1, 2, 3, 4, 5, 5, 5, 5]);
+        assert_eq!(scoring.get_multiplier(), MAX_MULTIPLIER);
+        assert_eq!(scoring.total, 30);
+        assert_eq!(scoring.get_combo_bonus(), 22);
+    }
+
+    #[test]
+    fn combo_ends_when_the_speed_runs_out() {
+        let registry = CollectableRegistry::default();
+        let mut scoring = Scoring::default();
+        eat_apple(&mut scoring, &registry, 0);
+        eat_apple(&mut scoring, &registry, 1);
+        let event = GameEvent::EffectExpired(SLOW);
+        assert!(scoring.on_event(&event, &registry, 0, 2).is_none());
+        assert_eq!(scoring.get_combo(2), 2);
+
+        let event = GameEvent::EffectExpired(SPEED);
+        assert!(scoring.on_event(&event, &registry, 0, 2).is_none());
+        assert_eq!(scoring.get_combo(2), 0);
+        assert_eq!(eat_apple(&mut scoring, &registry, 3), 1);
+    }
+
+    #[test]
+    fn golden_apples_keep_the_combo_going() {
+        let registry = CollectableRegistry::default();
+        let mut scoring = Scoring::default();
+        eat_apple(&mut scoring, &registry, 0);
+        let event = GameEvent::Collected(GOLDEN_APPLE);
+        let points = scoring
+            .on_event(&event, &registry, 0, 1)
+            .map(|event| event.points);
+        assert_eq!(points, Some(6));
+        assert_eq!(eat_apple(&mut scoring, &registry, 2), 3);
+    }
+}
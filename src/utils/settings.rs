@@ -0,0 +1,47 @@
+use std::fs::read_to_string;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    enums::{GameMode, RenderStyle, SnakeSkin},
+    helpers::{get_data_file_path, save_data_file},
+    sound::Volume,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Mode of the rounds started with "Start Game"
+    pub game_mode: GameMode,
+    /// Choose the field size from the terminal size when a game starts
+    pub auto_field_size: bool,
+    /// Name of the selected theme, an unknown name falls back to the classic theme
+    pub theme: String,
+    pub render_style: RenderStyle,
+    pub snake_skin: SnakeSkin,
+    pub sound_muted: bool,
+    pub volume: Volume,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+impl Settings {
+    /// Loads the settings from disk, falling back to the defaults
+    pub fn load() -> Self {
+        let path = get_data_file_path("settings.json");
+        let mut settings: Settings = match path.and_then(read_to_string) {
+            Ok(raw_string) => serde_json::from_str(&raw_string).unwrap_or_default(),
+            Err(_err) => Settings::default(),
+        };
+        settings.persistent = true;
+        settings
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if !self.persistent {
+            return Ok(());
+        }
+        save_data_file("settings.json", self)
+    }
+}
@@ -0,0 +1,248 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::settings::Settings;
+
+/// Something in the game that makes a sound
+#[derive(Clone, Copy, PartialEq)]
+pub enum SoundEvent {
+    /// The snake picked up an item
+    Collect,
+    Died,
+    /// The cursor moved to another entry of a list
+    MenuMove,
+    /// An entry was chosen or a setting changed
+    MenuSelect,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 4] = [
+        SoundEvent::Collect,
+        SoundEvent::Died,
+        SoundEvent::MenuMove,
+        SoundEvent::MenuSelect,
+    ];
+
+    /// Returns whether the sound is worth ringing the bell, which can not tell sounds apart
+    fn rings_bell(&self) -> bool {
+        matches!(self, SoundEvent::Collect | SoundEvent::Died)
+    }
+
+    /// Returns the tones of the sound as frequency in Hz and length in milliseconds
+    #[cfg(feature = "audio")]
+    fn get_tones(&self) -> &'static [(f32, u64)] {
+        match self {
+            SoundEvent::Collect => &[(660.0, 40), (990.0, 60)],
+            SoundEvent::Died => &[(330.0, 120), (220.0, 120), (110.0, 240)],
+            SoundEvent::MenuMove => &[(440.0, 20)],
+            SoundEvent::MenuSelect => &[(550.0, 30), (880.0, 40)],
+        }
+    }
+}
+
+/// Loudness of the sound effects in steps from 0 to [`Volume::MAX`]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Volume(u8);
+
+impl Default for Volume {
+    fn default() -> Self {
+        Volume(7)
+    }
+}
+
+impl Volume {
+    pub const MAX: u8 = 10;
+
+    pub fn get_step(&self) -> u8 {
+        self.0.min(Volume::MAX)
+    }
+
+    /// Returns the volume one step louder or quieter, it stops at the ends
+    pub fn changed(&self, louder: bool) -> Volume {
+        if louder {
+            Volume((self.get_step() + 1).min(Volume::MAX))
+        } else {
+            Volume(self.get_step().saturating_sub(1))
+        }
+    }
+
+    /// Returns the volume as a factor from 0.0 to 1.0
+    pub fn get_factor(&self) -> f32 {
+        self.get_step() as f32 / Volume::MAX as f32
+    }
+}
+
+/// A way to play sounds, implement it to play them through something else
+pub trait SoundBackend {
+    /// Starts the sound without waiting for it to end, `volume` goes from 0.0 to 1.0
+    fn play(&mut self, sound: SoundEvent, volume: f32);
+}
+
+/// Plays nothing, for games that are embedded or driven headless
+pub struct SilentBackend;
+
+impl SoundBackend for SilentBackend {
+    fn play(&mut self, _sound: SoundEvent, _volume: f32) {}
+}
+
+/// Rings the terminal bell, which works everywhere but has no volume and only one sound
+pub struct BellBackend;
+
+impl SoundBackend for BellBackend {
+    fn play(&mut self, sound: SoundEvent, _volume: f32) {
+        if sound.rings_bell() {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+}
+
+/// Plays short generated tones on the default audio device
+#[cfg(feature = "audio")]
+pub struct AudioBackend {
+    stream: rodio::OutputStream,
+}
+
+#[cfg(feature = "audio")]
+impl AudioBackend {
+    /// Opens the default audio device, returns `None` if there is none
+    pub fn new() -> Option<Self> {
+        let mut stream = rodio::OutputStreamBuilder::open_default_stream().ok()?;
+        stream.log_on_drop(false);
+        Some(AudioBackend { stream })
+    }
+}
+
+#[cfg(feature = "audio")]
+impl SoundBackend for AudioBackend {
+    fn play(&mut self, sound: SoundEvent, volume: f32) {
+        use rodio::{Source, source::SineWave};
+        use std::time::Duration;
+
+        let mut start = Duration::ZERO;
+        for (frequency, length) in sound.get_tones() {
+            let length = Duration::from_millis(*length);
+            // Pure sine waves are loud, even at full volume they stay quiet
+            let tone = SineWave::new(*frequency)
+                .take_duration(length)
+                .amplify(volume * 0.2)
+                .delay(start);
+            self.stream.mixer().add(tone);
+            start += length;
+        }
+    }
+}
+
+/// Plays the sound effects of the game through a backend, as loud as the settings allow
+pub struct Sound {
+    backend: Box<dyn SoundBackend>,
+}
+
+impl Default for Sound {
+    fn default() -> Self {
+        Self::silent()
+    }
+}
+
+impl Sound {
+    /// Uses the audio device if the `audio` feature is enabled and there is one,
+    /// the terminal bell otherwise
+    pub fn new() -> Self {
+        #[cfg(feature = "audio")]
+        if let Some(backend) = AudioBackend::new() {
+            return Self::with_backend(Box::new(backend));
+        }
+        Self::with_backend(Box::new(BellBackend))
+    }
+
+    pub fn silent() -> Self {
+        Self::with_backend(Box::new(SilentBackend))
+    }
+
+    pub fn with_backend(backend: Box<dyn SoundBackend>) -> Self {
+        Sound { backend }
+    }
+
+    /// Plays the sound unless it is muted in the settings
+    pub fn play(&mut self, sound: SoundEvent, settings: &Settings) {
+        if settings.sound_muted || settings.volume.get_step() == 0 {
+            return;
+        }
+        self.backend.play(sound, settings.volume.get_factor());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    type PlayedSounds = Rc<RefCell<Vec<(SoundEvent, f32)>>>;
+
+    /// Remembers every sound instead of playing it
+    struct RecordingBackend {
+        played: PlayedSounds,
+    }
+
+    impl SoundBackend for RecordingBackend {
+        fn play(&mut self, sound: SoundEvent, volume: f32) {
+            self.played.borrow_mut().push((sound, volume));
+        }
+    }
+
+    fn recording_sound() -> (Sound, PlayedSounds) {
+        let played = Rc::new(RefCell::new(vec![]));
+        let backend = RecordingBackend {
+            played: played.clone(),
+        };
+        (Sound::with_backend(Box::new(backend)), played)
+    }
+
+    /// Needs an output device, CI provides the ALSA null device through `~/.asoundrc`
+    #[cfg(feature = "audio")]
+    #[test]
+    fn audio_backend_plays_every_event() {
+        let mut backend = AudioBackend::new().expect("no audio output device to open");
+        for event in SoundEvent::ALL {
+            backend.play(event, 0.0);
+        }
+    }
+
+    #[test]
+    fn volume_stays_within_bounds() {
+        assert_eq!(Volume::default().get_step(), 7);
+        assert_eq!(Volume(Volume::MAX + 5).get_step(), Volume::MAX);
+        assert_eq!(Volume(Volume::MAX).changed(true).get_step(), Volume::MAX);
+        assert_eq!(Volume(0).changed(false).get_step(), 0);
+        assert_eq!(Volume(3).changed(true).get_step(), 4);
+        assert_eq!(Volume(0).get_factor(), 0.0);
+        assert_eq!(Volume(Volume::MAX).get_factor(), 1.0);
+    }
+
+    #[test]
+    fn plays_with_the_volume_of_the_settings() {
+        let (mut sound, played) = recording_sound();
+        let mut settings = Settings::default();
+        settings.volume = Volume(5);
+        sound.play(SoundEvent::Collect, &settings);
+        assert!(*played.borrow() == [(SoundEvent::Collect, 0.5)]);
+    }
+
+    #[test]
+    fn muted_or_silent_volume_plays_nothing() {
+        let (mut sound, played) = recording_sound();
+        let mut muted = Settings::default();
+        muted.sound_muted = true;
+        let mut silent = Settings::default();
+        silent.volume = Volume(0);
+        for event in SoundEvent::ALL {
+            sound.play(event, &muted);
+            sound.play(event, &silent);
+        }
+        assert!(played.borrow().is_empty());
+    }
+}
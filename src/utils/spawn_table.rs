@@ -0,0 +1,190 @@
+use rand::Rng;
+
+use crate::{
+    game::App,
+    utils::{
+        collectables::CollectableId, config::GameConfig, enums::GameMode,
+        registry::CollectableRegistry,
+    },
+};
+
+/// Describes when and how often a special item may appear on the field
+#[derive(Clone)]
+pub struct SpawnRule {
+    pub weight: u32,
+    /// Added to the weight for every level after the first
+    pub weight_per_level: u32,
+    pub min_snake_length: usize,
+    pub min_round_time: u64,
+    pub max_on_board: usize,
+    /// Seconds until the item disappears if it is not picked up
+    pub despawn_after: Option<u32>,
+}
+
+impl SpawnRule {
+    pub fn new(weight: u32) -> Self {
+        SpawnRule {
+            weight,
+            weight_per_level: 0,
+            min_snake_length: 0,
+            min_round_time: 0,
+            max_on_board: 1,
+            despawn_after: Some(20),
+        }
+    }
+
+    pub fn weight_per_level(mut self, weight_per_level: u32) -> Self {
+        self.weight_per_level = weight_per_level;
+        self
+    }
+
+    pub fn min_snake_length(mut self, min_snake_length: usize) -> Self {
+        self.min_snake_length = min_snake_length;
+        self
+    }
+
+    pub fn min_round_time(mut self, min_round_time: u64) -> Self {
+        self.min_round_time = min_round_time;
+        self
+    }
+
+    pub fn max_on_board(mut self, max_on_board: usize) -> Self {
+        self.max_on_board = max_on_board;
+        self
+    }
+
+    pub fn despawn_after(mut self, despawn_after: Option<u32>) -> Self {
+        self.despawn_after = despawn_after;
+        self
+    }
+
+    fn can_spawn(&self, collectable_type: CollectableId, app: &App) -> bool {
+        let on_board = app
+            .collectables
+            .iter()
+            .filter(|spawned| spawned.collectable_type == collectable_type)
+            .count();
+        app.snake.len() >= self.min_snake_length
+            && app.round_time >= self.min_round_time
+            && on_board < self.max_on_board
+    }
+}
+
+pub struct SpawnTable {
+    pub rules: Vec<(CollectableId, SpawnRule)>,
+    /// Range of seconds between two special items
+    pub spawn_interval: (u32, u32),
+}
+
+impl SpawnTable {
+    /// Builds the table out of the spawn rules of every registered collectable
+    pub fn new(config: &GameConfig, level: u32, registry: &CollectableRegistry) -> Self {
+        let rules = registry
+            .get_definitions()
+            .filter_map(|definition| {
+                let mut rule = definition.spawn_rule.clone()?;
+                rule.weight += rule.weight_per_level * (level - 1);
+                config.modifiers.item_mix.apply(definition, &mut rule);
+                Some((definition.id, rule))
+            })
+            .collect();
+        match config.game_mode {
+            GameMode::Classic
+            | GameMode::Daily
+            | GameMode::AppleRush
+            | GameMode::Survival
+            | GameMode::ShrinkingArena => SpawnTable {
+                rules,
+                spawn_interval: (10 - level.min(5), 20 - level.min(8)),
+            },
+            // Rounds are short, so items come more often
+            GameMode::TimeAttack => SpawnTable {
+                rules,
+                spawn_interval: (5, 10),
+            },
+        }
+    }
+
+    /// Picks an item type out of the rules that are currently allowed to spawn
+    ///
+    /// `roll` is a random number that decides which of them is picked
+    pub fn choose(&self, app: &App, roll: u32) -> Option<CollectableId> {
+        let candidates: Vec<&(CollectableId, SpawnRule)> = self
+            .rules
+            .iter()
+            .filter(|(collectable_type, rule)| {
+                rule.weight > 0 && rule.can_spawn(collectable_type, app)
+            })
+            .collect();
+        let total_weight: u32 = candidates.iter().map(|(_, rule)| rule.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+        let mut roll = roll % total_weight;
+        for (collectable_type, rule) in candidates {
+            if roll < rule.weight {
+                return Some(collectable_type);
+            }
+            roll -= rule.weight;
+        }
+        None
+    }
+
+    pub fn get_rule(&self, collectable_type: CollectableId) -> Option<&SpawnRule> {
+        self.rules
+            .iter()
+            .find(|(id, _)| *id == collectable_type)
+            .map(|(_, rule)| rule)
+    }
+
+    pub fn get_next_spawn_time(&self, rng: &mut impl Rng) -> u32 {
+        rng.random_range(self.spawn_interval.0..self.spawn_interval.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{
+        collectables::{BOMB, SLOW, SPEED},
+        scores::ScoreManager,
+    };
+
+    fn create_table(rules: Vec<(CollectableId, SpawnRule)>) -> SpawnTable {
+        SpawnTable {
+            rules,
+            spawn_interval: (10, 20),
+        }
+    }
+
+    #[test]
+    fn choose_follows_the_weights() {
+        let app = App::with_score_manager(ScoreManager::in_memory());
+        let table = create_table(vec![(SPEED, SpawnRule::new(1)), (SLOW, SpawnRule::new(3))]);
+        let picks: Vec<CollectableId> = (0..400)
+            .filter_map(|roll| table.choose(&app, roll))
+            .collect();
+        assert_eq!(picks.iter().filter(|id| **id == SPEED).count(), 100);
+        assert_eq!(picks.iter().filter(|id| **id == SLOW).count(), 300);
+        assert_eq!(table.choose(&app, 0), Some(SPEED));
+        assert_eq!(table.choose(&app, 1), Some(SLOW));
+        assert_eq!(table.choose(&app, 4), Some(SPEED));
+    }
+
+    #[test]
+    fn choose_skips_rules_that_can_not_spawn() {
+        let app = App::with_score_manager(ScoreManager::in_memory());
+        let table = create_table(vec![
+            (SPEED, SpawnRule::new(0)),
+            (
+                SLOW,
+                SpawnRule::new(5).min_snake_length(app.snake.len() + 1),
+            ),
+            (BOMB, SpawnRule::new(2).min_round_time(60)),
+        ]);
+        assert_eq!(table.choose(&app, 0), None);
+
+        let table = create_table(vec![(SPEED, SpawnRule::new(0)), (SLOW, SpawnRule::new(5))]);
+        assert!((0..10).all(|roll| table.choose(&app, roll) == Some(SLOW)));
+    }
+}
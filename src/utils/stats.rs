@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    collectables::CollectableId,
+    enums::{DeathCause, Turn},
+    game_events::GameEvent,
+    registry::CollectableRegistry,
+};
+
+/// What happened during a single round, shown on the game over screen
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Items that count as apples, like golden apples, are included
+    pub apples_eaten: u32,
+    /// How often every item was collected by its id in the registry,
+    /// in the order they were first collected
+    pub collected: Vec<(String, u32)>,
+    pub max_speed: i32,
+    /// Every turn of the snake together with the level it happened in
+    pub turns: Vec<(u32, Turn)>,
+    pub death_cause: Option<DeathCause>,
+    /// Position of the score on the leaderboard, starting at 1
+    pub rank: Option<usize>,
+    pub personal_best: bool,
+}
+
+impl RunStats {
+    pub fn on_event(&mut self, event: &GameEvent, level: u32, registry: &CollectableRegistry) {
+        match event {
+            GameEvent::Collected(collectable_type) => {
+                if registry
+                    .get(collectable_type)
+                    .is_some_and(|definition| definition.is_apple())
+                {
+                    self.apples_eaten += 1;
+                }
+                self.add_collected(collectable_type);
+            }
+            GameEvent::Died(cause) => self.death_cause = Some(*cause),
+            GameEvent::Turned(turn) => self.turns.push((level, *turn)),
+            _ => {}
+        }
+    }
+
+    /// Returns how often the item was collected in this round
+    pub fn get_collected(&self, collectable_type: CollectableId) -> u32 {
+        self.collected
+            .iter()
+            .find(|(id, _)| *id == collectable_type)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    fn add_collected(&mut self, collectable_type: CollectableId) {
+        match self
+            .collected
+            .iter_mut()
+            .find(|(id, _)| *id == collectable_type)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.collected.push((collectable_type.to_string(), 1)),
+        }
+    }
+}
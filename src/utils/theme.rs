@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::read_to_string;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    collectables::{
+        APPLE, BOMB, CollectableId, GHOST, GOLDEN_APPLE, MAGNET, PORTAL, REVERSE, SHIELD, SHRINK,
+        SLOW, SPEED,
+    },
+    helpers::get_data_file_path,
+    registry::CollectableDefinition,
+};
+
+/// Colors used by the renderer
+///
+/// User themes are read from `themes.json` in the data directory. Every color that a
+/// user theme leaves out is taken from the classic theme. Collectables and effects
+/// without an entry use the fallback color, or the color of their definition.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    pub snake_head: Color,
+    pub snake_body: Color,
+    pub text: Color,
+    pub accent: Color,
+    pub danger: Color,
+    pub speed_normal: Color,
+    pub speed_fast: Color,
+    pub speed_max: Color,
+    /// Colors of collectables by their id
+    pub collectables: HashMap<String, Color>,
+    /// Colors of effects by the id of the item that grants them
+    pub effects: HashMap<String, Color>,
+    pub fallback: Option<Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::classic()
+    }
+}
+
+impl Theme {
+    pub fn classic() -> Self {
+        Theme {
+            name: String::from("classic"),
+            snake_head: Color::Green,
+            snake_body: Color::LightGreen,
+            text: Color::White,
+            accent: Color::Green,
+            danger: Color::Red,
+            speed_normal: Color::Green,
+            speed_fast: Color::Yellow,
+            speed_max: Color::Red,
+            collectables: HashMap::new(),
+            effects: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: String::from("high-contrast"),
+            snake_head: Color::White,
+            snake_body: Color::LightGreen,
+            text: Color::White,
+            accent: Color::LightYellow,
+            danger: Color::LightRed,
+            speed_normal: Color::White,
+            speed_fast: Color::LightYellow,
+            speed_max: Color::LightRed,
+            collectables: get_color_map(&[
+                (APPLE, Color::LightRed),
+                (SPEED, Color::LightYellow),
+                (SLOW, Color::LightCyan),
+                (REVERSE, Color::LightBlue),
+                (GOLDEN_APPLE, Color::Yellow),
+                (SHRINK, Color::Cyan),
+                (GHOST, Color::Gray),
+                (MAGNET, Color::LightMagenta),
+                (BOMB, Color::DarkGray),
+                (PORTAL, Color::Magenta),
+                (SHIELD, Color::Blue),
+            ]),
+            effects: HashMap::new(),
+            fallback: None,
+        }
+    }
+
+    /// Okabe-Ito palette, which avoids telling red and green apart
+    pub fn deuteranopia() -> Self {
+        Theme {
+            name: String::from("deuteranopia"),
+            snake_head: Color::Rgb(0, 114, 178),
+            snake_body: Color::Rgb(86, 180, 233),
+            text: Color::White,
+            accent: Color::Rgb(86, 180, 233),
+            danger: Color::Rgb(230, 159, 0),
+            speed_normal: Color::Rgb(86, 180, 233),
+            speed_fast: Color::Rgb(240, 228, 66),
+            speed_max: Color::Rgb(230, 159, 0),
+            collectables: get_color_map(&[
+                (APPLE, Color::Rgb(230, 159, 0)),
+                (SPEED, Color::Rgb(240, 228, 66)),
+                (SLOW, Color::Rgb(0, 158, 115)),
+                (REVERSE, Color::Rgb(204, 121, 167)),
+                (GOLDEN_APPLE, Color::Rgb(255, 255, 255)),
+                (SHRINK, Color::Rgb(0, 158, 115)),
+                (GHOST, Color::Gray),
+                (MAGNET, Color::Rgb(204, 121, 167)),
+                (BOMB, Color::DarkGray),
+                (PORTAL, Color::Rgb(213, 94, 0)),
+                (SHIELD, Color::Rgb(0, 114, 178)),
+            ]),
+            effects: get_color_map(&[
+                ("speed", Color::Rgb(240, 228, 66)),
+                ("slow", Color::Rgb(0, 158, 115)),
+                ("magnet", Color::Rgb(204, 121, 167)),
+                ("shield", Color::Rgb(0, 114, 178)),
+            ]),
+            fallback: None,
+        }
+    }
+
+    /// Okabe-Ito palette without dark reds, which are hard to see with protanopia
+    pub fn protanopia() -> Self {
+        Theme {
+            name: String::from("protanopia"),
+            danger: Color::Rgb(240, 228, 66),
+            speed_max: Color::Rgb(240, 228, 66),
+            speed_fast: Color::Rgb(230, 159, 0),
+            ..Theme::deuteranopia()
+        }
+        .with_collectable_colors(&[
+            (APPLE, Color::Rgb(240, 228, 66)),
+            (SPEED, Color::Rgb(230, 159, 0)),
+            (PORTAL, Color::Rgb(204, 121, 167)),
+        ])
+    }
+
+    pub fn monochrome() -> Self {
+        Theme {
+            name: String::from("monochrome"),
+            snake_head: Color::White,
+            snake_body: Color::Gray,
+            text: Color::White,
+            accent: Color::White,
+            danger: Color::White,
+            speed_normal: Color::Gray,
+            speed_fast: Color::White,
+            speed_max: Color::White,
+            collectables: get_color_map(&[
+                (APPLE, Color::White),
+                (GOLDEN_APPLE, Color::White),
+                (BOMB, Color::DarkGray),
+            ]),
+            effects: HashMap::new(),
+            fallback: Some(Color::Gray),
+        }
+    }
+
+    pub fn solarized() -> Self {
+        let yellow = Color::Rgb(181, 137, 0);
+        let orange = Color::Rgb(203, 75, 22);
+        let red = Color::Rgb(220, 50, 47);
+        let magenta = Color::Rgb(211, 54, 130);
+        let violet = Color::Rgb(108, 113, 196);
+        let blue = Color::Rgb(38, 139, 210);
+        let cyan = Color::Rgb(42, 161, 152);
+        let green = Color::Rgb(133, 153, 0);
+        let base1 = Color::Rgb(147, 161, 161);
+        Theme {
+            name: String::from("solarized"),
+            snake_head: green,
+            snake_body: cyan,
+            text: base1,
+            accent: green,
+            danger: red,
+            speed_normal: green,
+            speed_fast: yellow,
+            speed_max: red,
+            collectables: get_color_map(&[
+                (APPLE, red),
+                (SPEED, yellow),
+                (SLOW, cyan),
+                (REVERSE, blue),
+                (GOLDEN_APPLE, orange),
+                (SHRINK, violet),
+                (GHOST, base1),
+                (MAGNET, magenta),
+                (BOMB, Color::Rgb(88, 110, 117)),
+                (PORTAL, violet),
+                (SHIELD, blue),
+            ]),
+            effects: get_color_map(&[
+                ("speed", yellow),
+                ("slow", cyan),
+                ("ghost", base1),
+                ("magnet", magenta),
+                ("shield", blue),
+            ]),
+            fallback: None,
+        }
+    }
+
+    /// Leaves every color to the terminal, used when `NO_COLOR` is set
+    pub fn no_color() -> Self {
+        Theme {
+            name: String::from("no-color"),
+            snake_head: Color::Reset,
+            snake_body: Color::Reset,
+            text: Color::Reset,
+            accent: Color::Reset,
+            danger: Color::Reset,
+            speed_normal: Color::Reset,
+            speed_fast: Color::Reset,
+            speed_max: Color::Reset,
+            collectables: HashMap::new(),
+            effects: HashMap::new(),
+            fallback: Some(Color::Reset),
+        }
+    }
+
+    pub fn get_builtin_themes() -> Vec<Theme> {
+        vec![
+            Theme::classic(),
+            Theme::high_contrast(),
+            Theme::deuteranopia(),
+            Theme::protanopia(),
+            Theme::monochrome(),
+            Theme::solarized(),
+        ]
+    }
+
+    /// Returns the built-in themes followed by the themes from `themes.json`
+    pub fn get_available_themes() -> Vec<Theme> {
+        let mut themes = Theme::get_builtin_themes();
+        if let Ok(raw_string) = get_data_file_path("themes.json").and_then(read_to_string) {
+            let user_themes: Vec<Theme> = serde_json::from_str(&raw_string).unwrap_or_default();
+            for theme in user_themes {
+                themes.retain(|builtin| builtin.name != theme.name);
+                themes.push(theme);
+            }
+        }
+        themes
+    }
+
+    /// Finds the theme with the given name, falling back to the classic theme
+    ///
+    /// If the `NO_COLOR` environment variable is set, colors are always disabled
+    pub fn load(name: &str) -> Self {
+        if is_no_color_set() {
+            return Theme::no_color();
+        }
+        Theme::get_available_themes()
+            .into_iter()
+            .find(|theme| theme.name == name)
+            .unwrap_or_default()
+    }
+
+    pub fn get_collectable_color(&self, id: CollectableId, default: Color) -> Color {
+        self.collectables
+            .get(id)
+            .copied()
+            .or(self.fallback)
+            .unwrap_or(default)
+    }
+
+    /// Color of the effect that the item grants
+    pub fn get_effect_color(&self, definition: &CollectableDefinition) -> Color {
+        self.effects
+            .get(definition.id)
+            .copied()
+            .or(self.fallback)
+            .unwrap_or(definition.color)
+    }
+
+    /// Color of a body segment, fading out from the body color at the head
+    /// (`progress` 0.0) to a darker shade at the tail (`progress` 1.0)
+    ///
+    /// Colors without a known RGB value are not faded
+    pub fn get_gradient_color(&self, progress: f64) -> Color {
+        let Some((red, green, blue)) = get_rgb(self.snake_body) else {
+            return self.snake_body;
+        };
+        let factor = 1.0 - 0.6 * progress.clamp(0.0, 1.0);
+        let fade = |value: u8| (value as f64 * factor) as u8;
+        Color::Rgb(fade(red), fade(green), fade(blue))
+    }
+
+    fn with_collectable_colors(mut self, colors: &[(&str, Color)]) -> Self {
+        self.collectables.extend(get_color_map(colors));
+        self
+    }
+}
+
+pub fn is_no_color_set() -> bool {
+    env::var("NO_COLOR").is_ok_and(|value| !value.is_empty())
+}
+
+/// Returns the usual xterm values of the named colors
+fn get_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(red, green, blue) => Some((red, green, blue)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((205, 0, 0)),
+        Color::Green => Some((0, 205, 0)),
+        Color::Yellow => Some((205, 205, 0)),
+        Color::Blue => Some((0, 0, 238)),
+        Color::Magenta => Some((205, 0, 205)),
+        Color::Cyan => Some((0, 205, 205)),
+        Color::Gray => Some((229, 229, 229)),
+        Color::DarkGray => Some((127, 127, 127)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((92, 92, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
+        Color::Reset | Color::Indexed(_) => None,
+    }
+}
+
+fn get_color_map(colors: &[(&str, Color)]) -> HashMap<String, Color> {
+    colors
+        .iter()
+        .map(|(name, color)| (name.to_string(), *color))
+        .collect()
+}